@@ -0,0 +1,35 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use wavesplatform::account::{PrivateKeyAccount, TESTNET};
+use wavesplatform::error::Error;
+use wavesplatform::node::{Node, TESTNET_URL};
+use wavesplatform::seed::*;
+use wavesplatform::transaction::*;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let node = Node::from_url(TESTNET_URL);
+
+    let phrase = generate_phrase();
+    let account = PrivateKeyAccount::from_seed(&phrase);
+
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        * 1000;
+    let tx = Transaction::new_alias(account.public_key(), "rhino", TESTNET, 100000, ts);
+    let signed_tx = account.sign_transaction(tx);
+
+    // A freshly generated account has no WAVES to pay the fee, so the node rejects the transaction
+    match node.broadcast(&signed_tx).await {
+        Ok(result) => println!("Broadcasted: {}", result.id()),
+        Err(Error::NodeApi(node_err)) => println!(
+            "Rejected with code {}: {}",
+            node_err.error(),
+            node_err.message()
+        ),
+        Err(err) => return Err(err.into()),
+    }
+
+    Ok(())
+}
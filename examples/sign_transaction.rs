@@ -9,7 +9,7 @@ fn main() {
     let account = PrivateKeyAccount::from_seed(&phrase);
     println!(
         "My TESTNET address: {}",
-        account.public_key().to_address(TESTNET).to_string()
+        account.public_key().to_address(TESTNET)
     );
 
     let ts = SystemTime::now()
@@ -17,8 +17,8 @@ fn main() {
         .unwrap()
         .as_secs()
         * 1000;
-    let tx = Transaction::new_alias(&account.public_key(), "rhino", TESTNET, 100000, ts);
-    println!("ID is {}", tx.id().to_string());
+    let tx = Transaction::new_alias(account.public_key(), "rhino", TESTNET, 100000, ts);
+    println!("ID is {}", tx.id());
     let ptx = account.sign_transaction(tx);
     println!(
         "Proofs are {:?}",
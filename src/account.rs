@@ -1,4 +1,6 @@
 mod address;
+mod custom_data;
+mod encryption;
 mod private_key;
 mod public_key;
 
@@ -7,17 +9,18 @@ use blake2::VarBlake2b;
 use curve25519_dalek::constants;
 use curve25519_dalek::scalar::Scalar;
 use ed25519_dalek::*;
-use rand;
-use rand::Rng;
+use rand::{CryptoRng, RngCore};
 use sha2::{Digest, Sha512};
 use sha3::Keccak256;
 
 pub use address::*;
+pub use custom_data::*;
+pub use encryption::*;
 pub use private_key::*;
 pub use public_key::*;
 
-const ADDRESS_VERSION: u8 = 1;
-const ADDRESS_LENGTH: usize = 26;
+pub(crate) const ADDRESS_VERSION: u8 = 1;
+pub(crate) const ADDRESS_LENGTH: usize = 26;
 
 /// MAINNET chainID
 pub const MAINNET: u8 = b'W';
@@ -32,16 +35,39 @@ static INITBUF: [u8; 32] = [
 ];
 
 pub(crate) fn sign(message: &[u8], secret_key: &[u8; SECRET_KEY_LENGTH]) -> [u8; SIGNATURE_LENGTH] {
+    sign_with_rng(message, secret_key, &mut rand::thread_rng())
+}
+
+pub(crate) fn sign_with_rng<R: RngCore + CryptoRng>(
+    message: &[u8],
+    secret_key: &[u8; SECRET_KEY_LENGTH],
+    rng: &mut R,
+) -> [u8; SIGNATURE_LENGTH] {
+    let mut random = [0u8; 64];
+    rng.fill_bytes(&mut random);
+    sign_with_random(message, secret_key, &random)
+}
+
+/// Signs with the nonce derived from the secret key and the message only, so that the same
+/// message always gets the same signature
+pub(crate) fn sign_deterministic(
+    message: &[u8],
+    secret_key: &[u8; SECRET_KEY_LENGTH],
+) -> [u8; SIGNATURE_LENGTH] {
+    sign_with_random(message, secret_key, &[0u8; 64])
+}
+
+fn sign_with_random(
+    message: &[u8],
+    secret_key: &[u8; SECRET_KEY_LENGTH],
+    random: &[u8; 64],
+) -> [u8; SIGNATURE_LENGTH] {
     let mut hash = Sha512::default();
-    hash.input(&INITBUF);
+    hash.input(INITBUF);
 
     hash.input(secret_key);
     hash.input(message);
-
-    let mut rand = rand::thread_rng();
-    let mut rndbuf: Vec<u8> = vec![0; 64];
-    (0..63).for_each(|i| rndbuf[i] = rand.gen::<u8>());
-    hash.input(&rndbuf);
+    hash.input(random);
 
     let rsc = Scalar::from_hash(hash);
     let r = (&rsc * &constants::ED25519_BASEPOINT_TABLE)
@@ -52,8 +78,8 @@ pub(crate) fn sign(message: &[u8], secret_key: &[u8; SECRET_KEY_LENGTH]) -> [u8;
     let pubkey = ed_pubkey.compress().to_bytes();
 
     hash = Sha512::default();
-    hash.input(&r);
-    hash.input(&pubkey);
+    hash.input(r);
+    hash.input(pubkey);
     hash.input(message);
     let s = (Scalar::from_hash(hash) * Scalar::from_bits(*secret_key)) + rsc;
 
@@ -85,6 +111,8 @@ mod tests {
     use super::*;
 
     use base58::FromBase58;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[test]
     fn test_hashes() {
@@ -101,6 +129,35 @@ mod tests {
         assert_eq!(secure_hash(secure_in), secure_out.as_slice());
     }
 
+    #[test]
+    fn test_sign_random() {
+        let account = PrivateKeyAccount::from_seed("test");
+        let sk = account.private_key();
+        let msg = "uncle".as_bytes();
+
+        let mut random = [7u8; 64];
+        let sig = sign_with_random(msg, &sk, &random);
+        random[63] = 8;
+        let other = sign_with_random(msg, &sk, &random);
+        assert_ne!(sig, other);
+        assert!(crate::util::sig_verify(
+            msg,
+            &account.public_key().0,
+            &other
+        ));
+
+        let sig = sign_deterministic(msg, &sk);
+        let expected = "2FmNRCzu9tEBDhLdZrVa8B2bJbum6FFsTCaQuTma7DD16ULHdv9JoikezKtNUkztgdqZANYZBVEfGHVbtC9jWpLu";
+        assert_eq!(sig.to_vec(), expected.from_base58().unwrap());
+        assert!(crate::util::sig_verify(msg, &account.public_key().0, &sig));
+        assert_ne!(sign(msg, &sk), sign(msg, &sk));
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let sig = sign_with_rng(msg, &sk, &mut rng);
+        assert_eq!(sign_with_rng(msg, &sk, &mut StdRng::seed_from_u64(42)), sig);
+        assert_ne!(sign_with_rng(msg, &sk, &mut rng), sig);
+    }
+
     #[test]
     fn test_private_key_from_seed() {
         let private_key_account = PrivateKeyAccount::from_seed("test");
@@ -129,6 +186,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_seed_nonce() {
+        let account = PrivateKeyAccount::from_seed("test");
+        assert_eq!(PrivateKeyAccount::from_seed_with_nonce("test", 0), account);
+
+        let accounts: Vec<_> = PrivateKeyAccount::iter_from_seed("test").take(3).collect();
+        assert_eq!(accounts[0], account);
+        assert_eq!(
+            accounts[2],
+            PrivateKeyAccount::from_seed_with_nonce("test", 2)
+        );
+        assert_ne!(accounts[1], accounts[0]);
+        assert_ne!(accounts[1], accounts[2]);
+
+        let last = PrivateKeyAccount::iter_from_seed("test").starting_from(u32::MAX);
+        assert_eq!(last.count(), 1);
+    }
+
     #[test]
     fn test_key_pair_to_string() {
         let account = PrivateKeyAccount::from_seed("test");
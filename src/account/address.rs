@@ -1,6 +1,7 @@
-use crate::account::ADDRESS_LENGTH;
+use crate::account::{secure_hash, ADDRESS_LENGTH, ADDRESS_VERSION};
+use crate::error::{from_base58, Error};
 
-use base58::{FromBase58, ToBase58};
+use base58::ToBase58;
 use std::fmt;
 
 /// An account possessing a address.
@@ -16,6 +17,9 @@ use std::fmt;
 ///
 /// Normally, the address starting with 3P refers to the Mainnet, and the address starting with 3M or 3N refers to Testnet or Stagenet.
 ///
+/// Addresses created from bytes or a base58 string are checked for the version byte and the
+/// checksum; use [`Address::validate()`] to also check the chain ID.
+///
 /// # Usage
 /// ```
 /// use wavesplatform::account::{PrivateKeyAccount, TESTNET};
@@ -40,16 +44,53 @@ impl Address {
     }
 
     /// Create an [`Address`] from the base58 string.
-    pub fn from_string(base58: &str) -> Address {
-        let mut bytes = [0u8; ADDRESS_LENGTH];
-        bytes.copy_from_slice(base58.from_base58().unwrap().as_slice()); ////map unwrap, handle bad length
-        Address(bytes)
+    pub fn from_string(base58: &str) -> Result<Address, Error> {
+        Address::checked(from_base58(base58)?)
     }
 
     /// Create an [`Address`] from inner byte value.
-    pub fn from_bytes(buffer: &[u8]) -> Address {
+    pub fn from_bytes(buffer: &[u8]) -> Result<Address, Error> {
+        if buffer.len() != ADDRESS_LENGTH {
+            return Err(Error::InvalidLength {
+                expected: ADDRESS_LENGTH,
+                actual: buffer.len(),
+            });
+        }
         let mut bytes = [0u8; ADDRESS_LENGTH];
-        bytes.copy_from_slice(&buffer[..ADDRESS_LENGTH]);
+        bytes.copy_from_slice(buffer);
+        Address::checked(bytes)
+    }
+
+    /// Validate an address entered by a user. Besides the version byte and the checksum, checks
+    /// that the address belongs to the `chain_id` network if one is given.
+    /// ```
+    /// use wavesplatform::account::{Address, MAINNET, TESTNET};
+    /// let address = "3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D";
+    /// assert!(Address::validate(address, Some(TESTNET)).is_ok());
+    /// assert!(Address::validate(address, Some(MAINNET)).is_err());
+    /// ```
+    pub fn validate(base58: &str, chain_id: Option<u8>) -> Result<Address, Error> {
+        let address = Address::from_string(base58)?;
+        match chain_id {
+            Some(expected) if expected != address.chain_id() => Err(Error::InvalidChainId {
+                expected,
+                actual: address.chain_id(),
+            }),
+            _ => Ok(address),
+        }
+    }
+
+    fn checked(bytes: [u8; ADDRESS_LENGTH]) -> Result<Address, Error> {
+        if bytes[0] != ADDRESS_VERSION {
+            return Err(Error::InvalidAddressVersion(bytes[0]));
+        }
+        if bytes[22..] != secure_hash(&bytes[..22])[..4] {
+            return Err(Error::InvalidChecksum);
+        }
+        Ok(Address(bytes))
+    }
+
+    pub(crate) fn from_array(bytes: [u8; ADDRESS_LENGTH]) -> Address {
         Address(bytes)
     }
 }
@@ -59,3 +100,47 @@ impl fmt::Display for Address {
         write!(f, "{}", self.0.to_base58())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::{MAINNET, TESTNET};
+
+    #[test]
+    fn test_address_errors() {
+        let address = Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D").unwrap();
+        assert_eq!(Address::from_bytes(address.to_bytes()).unwrap(), address);
+        assert!(matches!(
+            Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3"),
+            Err(Error::InvalidLength { expected: 26, .. })
+        ));
+        assert!(matches!(
+            Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3l"),
+            Err(Error::InvalidBase58)
+        ));
+        assert!(matches!(
+            Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3E"),
+            Err(Error::InvalidChecksum)
+        ));
+        let mut bytes = *address.to_bytes();
+        bytes[0] = 2;
+        assert!(matches!(
+            Address::from_bytes(&bytes),
+            Err(Error::InvalidAddressVersion(2))
+        ));
+        assert!(matches!(
+            Address::validate("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D", Some(MAINNET)),
+            Err(Error::InvalidChainId {
+                expected: MAINNET,
+                actual: TESTNET
+            })
+        ));
+        assert!(matches!(
+            Address::from_bytes(&[1; 27]),
+            Err(Error::InvalidLength {
+                expected: 26,
+                actual: 27
+            })
+        ));
+    }
+}
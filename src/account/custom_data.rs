@@ -0,0 +1,118 @@
+use crate::bytebuffer::Buffer;
+use crate::transaction::DataEntry;
+
+use std::borrow::Cow;
+
+/// Bytes that start custom data signed by Waves wallets, followed by the custom data version.
+/// Transaction bytes never start with them, so a signature of custom data cannot be used as a
+/// signature of a transaction.
+const CUSTOM_DATA_PREFIX: [u8; 3] = [255, 255, 255];
+/// Prefix of the data signed to log in to a website with a Waves wallet
+const AUTH_PREFIX: &str = "WavesWalletAuthentication";
+
+/// Custom data signed by Waves wallets with `signCustomData`: arbitrary bytes (version 1) or typed
+/// key-value entries (version 2).
+///
+/// # Usage
+/// ```
+/// use wavesplatform::account::{CustomData, PrivateKeyAccount};
+/// use wavesplatform::transaction::DataEntry;
+/// let account = PrivateKeyAccount::from_seed("seed");
+/// let data = CustomData::V2(vec![DataEntry::Integer("nonce".into(), 42)]);
+/// let signature = account.sign_custom_data(&data);
+/// assert!(account.public_key().verify_custom_data(&data, &signature));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CustomData<'a> {
+    V1(Cow<'a, [u8]>),
+    V2(Vec<DataEntry<'a>>),
+}
+
+impl<'a> CustomData<'a> {
+    /// Returns the signed bytes: the prefix and the version, followed by the bytes for version 1,
+    /// or by the number of entries and the entries in the Data transaction format for version 2
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Buffer::new();
+        buf.bytes(&CUSTOM_DATA_PREFIX);
+        match self {
+            CustomData::V1(bytes) => {
+                buf.byte(1).bytes(bytes);
+            }
+            CustomData::V2(entries) => {
+                buf.byte(2).size(entries.len());
+                for entry in entries {
+                    buf.data_entry(entry);
+                }
+            }
+        }
+        Vec::from(buf.as_slice())
+    }
+}
+
+/// Returns the bytes signed for a text message, which wallets sign as version 1 custom data with
+/// the UTF-8 bytes of the message
+pub fn message_bytes(message: &str) -> Vec<u8> {
+    CustomData::V1(message.as_bytes().into()).to_bytes()
+}
+
+/// Returns the bytes signed to authenticate with a Waves wallet on the given host: the
+/// `WavesWalletAuthentication` prefix, the host and the data requested by the website, each as a
+/// string with its length
+pub fn auth_bytes(host: &str, data: &str) -> Vec<u8> {
+    let mut buf = Buffer::new();
+    buf.array(AUTH_PREFIX.as_bytes())
+        .array(host.as_bytes())
+        .array(data.as_bytes());
+    Vec::from(buf.as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::PrivateKeyAccount;
+
+    #[test]
+    fn test_custom_data_bytes() {
+        let data = CustomData::V1(vec![1, 2, 3].into());
+        assert_eq!(data.to_bytes(), vec![255, 255, 255, 1, 1, 2, 3]);
+
+        let data = CustomData::V2(vec![
+            DataEntry::Boolean("b".into(), true),
+            DataEntry::String("s".into(), "hi".into()),
+        ]);
+        assert_eq!(
+            data.to_bytes(),
+            vec![255, 255, 255, 2, 0, 2, 0, 1, b'b', 1, 1, 0, 1, b's', 3, 0, 2, b'h', b'i']
+        );
+
+        assert_eq!(message_bytes("hi"), vec![255, 255, 255, 1, b'h', b'i']);
+
+        let mut expected = vec![0, 25];
+        expected.extend_from_slice(b"WavesWalletAuthentication");
+        expected.extend_from_slice(&[0, 11]);
+        expected.extend_from_slice(b"example.com");
+        expected.extend_from_slice(&[0, 5]);
+        expected.extend_from_slice(b"nonce");
+        assert_eq!(auth_bytes("example.com", "nonce"), expected);
+    }
+
+    #[test]
+    fn test_auth() {
+        let account = PrivateKeyAccount::from_seed("seed");
+        let signature = account.sign_auth("example.com", "nonce");
+        let public_key = account.public_key();
+        assert!(public_key.verify_auth("example.com", "nonce", &signature));
+        assert!(!public_key.verify_auth("evil.com", "nonce", &signature));
+        assert!(!public_key.verify_message("nonce", &signature));
+    }
+
+    #[test]
+    fn test_message() {
+        let account = PrivateKeyAccount::from_seed("seed");
+        let signature = account.sign_message("hello");
+        assert!(account.public_key().verify_message("hello", &signature));
+        assert!(!account.public_key().verify_message("hello!", &signature));
+        let other = PrivateKeyAccount::from_seed("other");
+        assert!(!other.public_key().verify_message("hello", &signature));
+    }
+}
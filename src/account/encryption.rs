@@ -0,0 +1,217 @@
+use crate::account::{PrivateKeyAccount, PublicKeyAccount};
+use crate::error::Error;
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit, KeyIvInit, StreamCipher};
+use aes::Aes256;
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use curve25519_dalek::scalar::Scalar;
+use hmac::{Hmac, Mac};
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+
+/// AES-256 in the counter mode with a 32-bit big-endian counter, as in the Waves JS library
+type Aes256Ctr = ctr::Ctr32BE<Aes256>;
+
+/// Length of a shared key and of a content encryption key
+pub const SHARED_KEY_LENGTH: usize = 32;
+/// Version of the encrypted message format
+const ENCRYPTION_VERSION: u8 = 1;
+const BLOCK_LENGTH: usize = 16;
+/// Length of the content encryption key encrypted with AES-ECB, including a block of padding
+const ENCRYPTED_KEY_LENGTH: usize = SHARED_KEY_LENGTH + BLOCK_LENGTH;
+const MAC_LENGTH: usize = 32;
+const IV_LENGTH: usize = 16;
+/// Length of everything in an encrypted message but the encrypted content
+const HEADER_LENGTH: usize = 1 + ENCRYPTED_KEY_LENGTH + 2 * MAC_LENGTH + IV_LENGTH;
+
+impl PrivateKeyAccount {
+    /// Derives the key shared between this account and the account of `public_key` for
+    /// [`encrypt_message`] and [`decrypt_message`]. Both accounts derive the same key from their
+    /// own private key and the public key of the other one, given the same `prefix`. It is the
+    /// X25519 shared secret, hashed with HMAC-SHA256 keyed by the SHA-256 hash of the prefix, as
+    /// `sharedKey` of the Waves JS library does.
+    ///
+    /// # Usage
+    /// ```
+    /// use wavesplatform::account::{decrypt_message, encrypt_message, PrivateKeyAccount};
+    /// let alice = PrivateKeyAccount::from_seed("alice");
+    /// let bob = PrivateKeyAccount::from_seed("bob");
+    ///
+    /// let key = alice.shared_key(bob.public_key(), "waves");
+    /// let encrypted = encrypt_message(&key, b"hello, Bob");
+    ///
+    /// let key = bob.shared_key(alice.public_key(), "waves");
+    /// assert_eq!(decrypt_message(&key, &encrypted).unwrap(), b"hello, Bob");
+    /// ```
+    pub fn shared_key(
+        &self,
+        public_key: &PublicKeyAccount,
+        prefix: &str,
+    ) -> [u8; SHARED_KEY_LENGTH] {
+        let secret = x25519(&self.private_key(), public_key.to_bytes());
+        let mut mac = new_hmac(&Sha256::digest(prefix.as_bytes()));
+        mac.input(&secret);
+        mac.result().code().into()
+    }
+}
+
+/// Encrypts a message with a key shared with the recipient, see [`PrivateKeyAccount::shared_key`].
+/// The message is encrypted with a random content key using AES-CTR, and the content key is
+/// encrypted with the shared key. Both are authenticated with HMAC-SHA256, so that tampered
+/// messages fail to decrypt. The format is the one of `messageEncrypt` of the Waves JS library.
+pub fn encrypt_message(shared_key: &[u8; SHARED_KEY_LENGTH], message: &[u8]) -> Vec<u8> {
+    encrypt_message_with_rng(shared_key, message, &mut rand::thread_rng())
+}
+
+/// Encrypts a message like [`encrypt_message`], generating the content key and the IV with the
+/// given RNG.
+pub fn encrypt_message_with_rng<R: RngCore + CryptoRng>(
+    shared_key: &[u8; SHARED_KEY_LENGTH],
+    message: &[u8],
+    rng: &mut R,
+) -> Vec<u8> {
+    let mut cek = [0u8; SHARED_KEY_LENGTH];
+    let mut iv = [0u8; IV_LENGTH];
+    rng.fill_bytes(&mut cek);
+    rng.fill_bytes(&mut iv);
+
+    let mut content = message.to_vec();
+    Aes256Ctr::new(&cek.into(), &iv.into()).apply_keystream(&mut content);
+
+    // the content key is encrypted with AES-ECB and PKCS#7 padding, i.e. a whole block of padding
+    let mut encrypted_key = [BLOCK_LENGTH as u8; ENCRYPTED_KEY_LENGTH];
+    encrypted_key[..SHARED_KEY_LENGTH].copy_from_slice(&cek);
+    let cipher = Aes256::new(shared_key.into());
+    for block in encrypted_key.chunks_exact_mut(BLOCK_LENGTH) {
+        cipher.encrypt_block(GenericArray::from_mut_slice(block));
+    }
+
+    let mut result = Vec::with_capacity(HEADER_LENGTH + message.len());
+    result.push(ENCRYPTION_VERSION);
+    result.extend_from_slice(&encrypted_key);
+    result.extend_from_slice(&key_mac(shared_key, &cek, &iv).result().code());
+    result.extend_from_slice(&message_mac(&cek, message).result().code());
+    result.extend_from_slice(&iv);
+    result.extend_from_slice(&content);
+    result
+}
+
+/// Decrypts a message made by [`encrypt_message`] with the same shared key. Fails with
+/// [`Error::InvalidSharedKey`] if the message was encrypted with another key, and with
+/// [`Error::InvalidMessage`] if the message was tampered with.
+pub fn decrypt_message(
+    shared_key: &[u8; SHARED_KEY_LENGTH],
+    encrypted: &[u8],
+) -> Result<Vec<u8>, Error> {
+    if encrypted.len() < HEADER_LENGTH {
+        return Err(Error::InvalidLength {
+            expected: HEADER_LENGTH,
+            actual: encrypted.len(),
+        });
+    }
+    if encrypted[0] != ENCRYPTION_VERSION {
+        return Err(Error::UnsupportedEncryptionVersion(encrypted[0]));
+    }
+    let (encrypted_key, rest) = encrypted[1..].split_at(ENCRYPTED_KEY_LENGTH);
+    let (cek_mac, rest) = rest.split_at(MAC_LENGTH);
+    let (content_mac, rest) = rest.split_at(MAC_LENGTH);
+    let (iv, content) = rest.split_at(IV_LENGTH);
+
+    let mut cek = [0u8; SHARED_KEY_LENGTH];
+    cek.copy_from_slice(&encrypted_key[..SHARED_KEY_LENGTH]);
+    let cipher = Aes256::new(shared_key.into());
+    for block in cek.chunks_exact_mut(BLOCK_LENGTH) {
+        cipher.decrypt_block(GenericArray::from_mut_slice(block));
+    }
+    if key_mac(shared_key, &cek, iv).verify(cek_mac).is_err() {
+        return Err(Error::InvalidSharedKey);
+    }
+
+    let mut message = content.to_vec();
+    Aes256Ctr::new(&cek.into(), GenericArray::from_slice(iv)).apply_keystream(&mut message);
+    if message_mac(&cek, &message).verify(content_mac).is_err() {
+        return Err(Error::InvalidMessage);
+    }
+    Ok(message)
+}
+
+fn x25519(secret_key: &[u8; SHARED_KEY_LENGTH], public_key: &[u8; SHARED_KEY_LENGTH]) -> [u8; 32] {
+    let mut scalar = *secret_key;
+    scalar[0] &= 248;
+    scalar[31] &= 127;
+    scalar[31] |= 64;
+    (MontgomeryPoint(*public_key) * Scalar::from_bits(scalar)).to_bytes()
+}
+
+fn new_hmac(key: &[u8]) -> Hmac<Sha256> {
+    Hmac::new_varkey(key).expect("HMAC accepts keys of any length")
+}
+
+fn key_mac(shared_key: &[u8], cek: &[u8], iv: &[u8]) -> Hmac<Sha256> {
+    let mut mac = new_hmac(shared_key);
+    mac.input(cek);
+    mac.input(iv);
+    mac
+}
+
+fn message_mac(cek: &[u8], message: &[u8]) -> Hmac<Sha256> {
+    let mut mac = new_hmac(cek);
+    mac.input(message);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex(hex: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_x25519() {
+        // test vector of RFC 7748, section 6.1
+        let secret = from_hex("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+        let public = from_hex("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f");
+        let shared = from_hex("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        assert_eq!(x25519(&secret, &public), shared);
+    }
+
+    #[test]
+    fn test_encryption() {
+        let alice = PrivateKeyAccount::from_seed("alice");
+        let bob = PrivateKeyAccount::from_seed("bob");
+        let key = alice.shared_key(bob.public_key(), "waves");
+        assert_eq!(key, bob.shared_key(alice.public_key(), "waves"));
+        assert_ne!(key, bob.shared_key(alice.public_key(), "other"));
+
+        let encrypted = encrypt_message(&key, b"attachment");
+        assert_eq!(encrypted.len(), HEADER_LENGTH + 10);
+        assert_ne!(encrypted, encrypt_message(&key, b"attachment"));
+        assert_eq!(decrypt_message(&key, &encrypted).unwrap(), b"attachment");
+        assert!(decrypt_message(&key, &encrypt_message(&key, b""))
+            .unwrap()
+            .is_empty());
+
+        let other_key = alice.shared_key(alice.public_key(), "waves");
+        assert!(matches!(
+            decrypt_message(&other_key, &encrypted),
+            Err(Error::InvalidSharedKey)
+        ));
+        let mut tampered = encrypted.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            decrypt_message(&key, &tampered),
+            Err(Error::InvalidMessage)
+        ));
+        assert!(matches!(
+            decrypt_message(&key, &encrypted[..HEADER_LENGTH - 1]),
+            Err(Error::InvalidLength { .. })
+        ));
+    }
+}
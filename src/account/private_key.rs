@@ -1,10 +1,15 @@
-use crate::account::{secure_hash, sign, PublicKeyAccount};
-use crate::transaction::{ProvenTransaction, Transaction};
+use crate::account::{
+    auth_bytes, message_bytes, secure_hash, sign, sign_deterministic, sign_with_rng, CustomData,
+    PublicKeyAccount,
+};
+use crate::error::Error;
+use crate::transaction::{Order, ProvenOrder, ProvenTransaction, Transaction};
 
 use base58::ToBase58;
 use curve25519_dalek::constants;
 use curve25519_dalek::scalar::Scalar;
 use ed25519_dalek::{PUBLIC_KEY_LENGTH, SECRET_KEY_LENGTH, SIGNATURE_LENGTH};
+use rand::{CryptoRng, RngCore};
 use sha2::{Digest, Sha256};
 use std::fmt;
 
@@ -55,15 +60,31 @@ impl PrivateKeyAccount {
 
     /// Create an [`PrivateKeyAccount`] from seed string.
     pub fn from_seed(seed: &str) -> PrivateKeyAccount {
-        let seed_bytes = seed.as_bytes().to_vec();
-        let nonce = [0, 0, 0, 0].to_vec();
+        PrivateKeyAccount::from_seed_with_nonce(seed, 0)
+    }
 
-        let mut sk = [0u8; SECRET_KEY_LENGTH];
+    /// Create an [`PrivateKeyAccount`] from seed string and nonce. Waves wallets derive additional
+    /// accounts from the same seed by incrementing the nonce, the first account has the nonce 0.
+    pub fn from_seed_with_nonce(seed: &str, nonce: u32) -> PrivateKeyAccount {
+        let seed_bytes = seed.as_bytes().to_vec();
+        let nonce = nonce.to_be_bytes().to_vec();
 
         let acc_seed = secure_hash([nonce, seed_bytes].concat().as_slice());
-        let hash_seed = &Sha256::digest(acc_seed.as_slice());
+        PrivateKeyAccount::from_account_seed(&acc_seed)
+    }
+
+    /// Create an [`PrivateKeyAccount`] from the account seed, the hash of the nonce and the seed
+    /// that the node wallet stores for each account.
+    pub(crate) fn from_account_seed(acc_seed: &[u8]) -> PrivateKeyAccount {
+        let mut sk = [0u8; SECRET_KEY_LENGTH];
+        let hash_seed = &Sha256::digest(acc_seed);
 
         sk.copy_from_slice(hash_seed);
+        PrivateKeyAccount::from_private_key(sk)
+    }
+
+    /// Create an [`PrivateKeyAccount`] from private key bytes, deriving the public key.
+    pub fn from_private_key(mut sk: [u8; SECRET_KEY_LENGTH]) -> PrivateKeyAccount {
         sk[0] &= 248;
         sk[31] &= 127;
         sk[31] |= 64;
@@ -73,11 +94,44 @@ impl PrivateKeyAccount {
         PrivateKeyAccount(sk, PublicKeyAccount(pk))
     }
 
+    /// Iterates over the accounts of the seed string with sequential nonces starting from 0.
+    ///
+    /// # Usage
+    /// ```
+    /// use wavesplatform::account::{PrivateKeyAccount, TESTNET};
+    /// for account in PrivateKeyAccount::iter_from_seed("seed").take(3) {
+    ///     println!("{}", account.public_key().to_address(TESTNET));
+    /// }
+    /// ```
+    pub fn iter_from_seed(seed: &str) -> SeedAccounts<'_> {
+        SeedAccounts {
+            seed,
+            nonce: Some(0),
+        }
+    }
+
     /// Signs internal byte values.
     pub fn sign_bytes(&self, data: &[u8]) -> [u8; SIGNATURE_LENGTH] {
         sign(data, &self.0)
     }
 
+    /// Signs internal byte values with the random part of the nonce taken from the given RNG
+    /// instead of the thread-local one.
+    pub fn sign_bytes_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        data: &[u8],
+        rng: &mut R,
+    ) -> [u8; SIGNATURE_LENGTH] {
+        sign_with_rng(data, &self.0, rng)
+    }
+
+    /// Signs internal byte values deterministically: the nonce depends only on the private key
+    /// and the data, so the same data always gets the same signature. Useful for tests and
+    /// reproducible signing.
+    pub fn sign_bytes_deterministic(&self, data: &[u8]) -> [u8; SIGNATURE_LENGTH] {
+        sign_deterministic(data, &self.0)
+    }
+
     /// Signs [`Transaction`] struct.
     pub fn sign_transaction<'a>(&self, tx: Transaction<'a>) -> ProvenTransaction<'a> {
         let signature = self.sign_bytes(&tx.to_bytes());
@@ -86,6 +140,90 @@ impl PrivateKeyAccount {
             proofs: vec![signature.to_vec()],
         }
     }
+
+    /// Signs [`Transaction`] struct with the given RNG, see [`PrivateKeyAccount::sign_bytes_with_rng`].
+    pub fn sign_transaction_with_rng<'a, R: RngCore + CryptoRng>(
+        &self,
+        tx: Transaction<'a>,
+        rng: &mut R,
+    ) -> ProvenTransaction<'a> {
+        let signature = self.sign_bytes_with_rng(&tx.to_bytes(), rng);
+        tx.with_proofs(vec![signature.to_vec()])
+    }
+
+    /// Signs [`Transaction`] struct deterministically, see
+    /// [`PrivateKeyAccount::sign_bytes_deterministic`].
+    /// ```
+    /// use wavesplatform::account::{PrivateKeyAccount, TESTNET};
+    /// use wavesplatform::transaction::Transaction;
+    /// let account = PrivateKeyAccount::from_seed("seed");
+    /// let tx = Transaction::new_alias(account.public_key(), "rhino", TESTNET, 100000, 1536000000000);
+    /// let signed_tx = account.sign_transaction_deterministic(tx.clone());
+    /// assert_eq!(account.sign_transaction_deterministic(tx), signed_tx);
+    /// ```
+    pub fn sign_transaction_deterministic<'a>(&self, tx: Transaction<'a>) -> ProvenTransaction<'a> {
+        let signature = self.sign_bytes_deterministic(&tx.to_bytes());
+        tx.with_proofs(vec![signature.to_vec()])
+    }
+
+    /// Signs the transaction of [`ProvenTransaction`] and sets the signature as the proof at the
+    /// given index, keeping the other proofs. Used to collect the signatures of a multisig account.
+    pub fn sign_proof(&self, ptx: &mut ProvenTransaction, index: usize) -> Result<(), Error> {
+        let signature = self.sign_bytes(&ptx.tx.to_bytes());
+        ptx.add_proof(index, signature.to_vec())
+    }
+
+    /// Signs [`CustomData`] the way Waves wallets do with `signCustomData`.
+    pub fn sign_custom_data(&self, data: &CustomData) -> [u8; SIGNATURE_LENGTH] {
+        self.sign_bytes(&data.to_bytes())
+    }
+
+    /// Signs a text message the way Waves wallets do with `signMessage`, see [`message_bytes`].
+    pub fn sign_message(&self, message: &str) -> [u8; SIGNATURE_LENGTH] {
+        self.sign_bytes(&message_bytes(message))
+    }
+
+    /// Signs an authentication request of the website on `host`, see [`auth_bytes`].
+    pub fn sign_auth(&self, host: &str, data: &str) -> [u8; SIGNATURE_LENGTH] {
+        self.sign_bytes(&auth_bytes(host, data))
+    }
+
+    /// Signs [`Order`] struct.
+    pub fn sign_order(&self, order: Order) -> ProvenOrder {
+        let signature = self.sign_bytes(&order.to_bytes());
+        ProvenOrder {
+            order,
+            proofs: vec![signature.to_vec()],
+        }
+    }
+}
+
+/// Iterator over the accounts of a seed string with sequential nonces, see
+/// [`PrivateKeyAccount::iter_from_seed`].
+#[derive(Debug, Clone)]
+pub struct SeedAccounts<'a> {
+    seed: &'a str,
+    nonce: Option<u32>,
+}
+
+impl<'a> SeedAccounts<'a> {
+    /// Starts the iteration from the given nonce instead of 0.
+    pub fn starting_from(self, nonce: u32) -> SeedAccounts<'a> {
+        SeedAccounts {
+            nonce: Some(nonce),
+            ..self
+        }
+    }
+}
+
+impl<'a> Iterator for SeedAccounts<'a> {
+    type Item = PrivateKeyAccount;
+
+    fn next(&mut self) -> Option<PrivateKeyAccount> {
+        let nonce = self.nonce?;
+        self.nonce = nonce.checked_add(1);
+        Some(PrivateKeyAccount::from_seed_with_nonce(self.seed, nonce))
+    }
 }
 
 impl fmt::Debug for PrivateKeyAccount {
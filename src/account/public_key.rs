@@ -1,7 +1,10 @@
-use crate::account::{secure_hash, Address, ADDRESS_LENGTH, ADDRESS_VERSION};
+use crate::account::{
+    auth_bytes, message_bytes, secure_hash, Address, CustomData, ADDRESS_LENGTH, ADDRESS_VERSION,
+};
+use crate::util::sig_verify;
 
 use base58::ToBase58;
-use ed25519_dalek::PUBLIC_KEY_LENGTH;
+use ed25519_dalek::{PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH};
 use std::fmt;
 
 /// An account possessing a public key. Using `PublicKeyAccount` you can get the address.
@@ -41,7 +44,27 @@ impl PublicKeyAccount {
         buf[2..22].copy_from_slice(&secure_hash(&self.0)[..20]);
         let checksum = &secure_hash(&buf[..22])[..4];
         buf[22..].copy_from_slice(checksum);
-        Address::from_bytes(&buf)
+        Address::from_array(buf)
+    }
+
+    /// Verifies the signature of [`CustomData`] made with the private key of this account.
+    pub fn verify_custom_data(
+        &self,
+        data: &CustomData,
+        signature: &[u8; SIGNATURE_LENGTH],
+    ) -> bool {
+        sig_verify(&data.to_bytes(), &self.0, signature)
+    }
+
+    /// Verifies the signature of a text message made with the private key of this account.
+    pub fn verify_message(&self, message: &str, signature: &[u8; SIGNATURE_LENGTH]) -> bool {
+        sig_verify(&message_bytes(message), &self.0, signature)
+    }
+
+    /// Verifies the signature of an authentication request of the website on `host` made with the
+    /// private key of this account.
+    pub fn verify_auth(&self, host: &str, data: &str, signature: &[u8; SIGNATURE_LENGTH]) -> bool {
+        sig_verify(&auth_bytes(host, data), &self.0, signature)
     }
 }
 
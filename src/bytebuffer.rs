@@ -1,5 +1,28 @@
-use crate::transaction::{Asset, DataEntry};
-use base58::*;
+use crate::account::{Address, PublicKeyAccount, ADDRESS_LENGTH};
+use crate::transaction::{
+    Arg, Asset, DataEntry, FunctionCall, ParseError, Recipient, TransactionId, HASH_LENGTH,
+};
+use crate::util::Alias;
+use ed25519_dalek::PUBLIC_KEY_LENGTH;
+
+/// Version byte of an alias recipient
+const ALIAS_VERSION: u8 = 2;
+
+/// Type byte of a data entry that deletes the key
+const DELETE_DATA_TYPE: u8 = 0xff;
+
+// Tags of the RIDE expressions that make up a serialized function call
+const E_LONG: u8 = 0;
+const E_BYTES: u8 = 1;
+const E_STRING: u8 = 2;
+const E_TRUE: u8 = 6;
+const E_FALSE: u8 = 7;
+const E_FUNCALL: u8 = 9;
+const E_ARR: u8 = 11;
+/// Function header of a user (dApp) function
+const FH_USER: u8 = 1;
+/// Version of the proofs block in signed transaction and order bytes
+const PROOFS_VERSION: u8 = 1;
 
 pub(crate) struct Buffer {
     buf: Vec<u8>,
@@ -50,15 +73,17 @@ impl Buffer {
         self
     }
 
-    pub fn recipient(&mut self, chain_id: u8, recipient: &str) -> &mut Buffer {
-        if recipient.len() <= 30 {
-            // assume an alias
-            self.byte(0x02)
-                .byte(chain_id)
-                .size(recipient.len())
-                .bytes(recipient.as_bytes())
-        } else {
-            self.bytes(recipient.from_base58().unwrap().as_slice())
+    pub fn int(&mut self, n: u32) -> &mut Buffer {
+        self.bytes(&n.to_be_bytes())
+    }
+
+    pub fn recipient(&mut self, recipient: &Recipient) -> &mut Buffer {
+        match recipient {
+            Recipient::Address(address) => self.bytes(address.to_bytes()),
+            Recipient::Alias { alias, chain_id } => self
+                .byte(ALIAS_VERSION)
+                .byte(*chain_id)
+                .array(alias.to_string().as_bytes()),
         }
     }
 
@@ -74,23 +99,272 @@ impl Buffer {
         self.bytes(&asset.to_bytes())
     }
 
-    pub fn asset_opt(&mut self, asset: &Option<&Asset>) -> &mut Buffer {
+    pub fn asset_opt(&mut self, asset: Option<&Asset>) -> &mut Buffer {
         match asset {
             Some(aid) => self.byte(1).asset(aid),
             None => self.byte(0),
         }
     }
 
+    pub fn script_opt(&mut self, script: Option<&[u8]>) -> &mut Buffer {
+        match script {
+            Some(bytes) => self.byte(1).array(bytes),
+            None => self.byte(0),
+        }
+    }
+
     pub fn data_entry(&mut self, e: &DataEntry) -> &mut Buffer {
-        match *e {
-            DataEntry::Integer(key, val) => self.array(key.as_bytes()).byte(0).long(val),
-            DataEntry::Boolean(key, val) => self.array(key.as_bytes()).byte(1).boolean(val),
+        match e {
+            DataEntry::Integer(key, val) => self.array(key.as_bytes()).byte(0).long(*val),
+            DataEntry::Boolean(key, val) => self.array(key.as_bytes()).byte(1).boolean(*val),
             DataEntry::Binary(key, val) => self.array(key.as_bytes()).byte(2).array(val),
             DataEntry::String(key, val) => self.array(key.as_bytes()).byte(3).array(val.as_bytes()),
+            DataEntry::Delete(key) => self.array(key.as_bytes()).byte(DELETE_DATA_TYPE),
         }
     }
 
+    /// Writes the function call as a serialized RIDE expression
+    pub fn function_call(&mut self, call: &FunctionCall) -> &mut Buffer {
+        self.byte(E_FUNCALL)
+            .byte(FH_USER)
+            .int(call.function.len() as u32)
+            .bytes(call.function.as_bytes())
+            .args(&call.args)
+    }
+
+    fn args(&mut self, args: &[Arg]) -> &mut Buffer {
+        self.int(args.len() as u32);
+        for arg in args {
+            match arg {
+                Arg::Integer(value) => self.byte(E_LONG).long(*value as u64),
+                Arg::Binary(value) => self.byte(E_BYTES).int(value.len() as u32).bytes(value),
+                Arg::String(value) => self
+                    .byte(E_STRING)
+                    .int(value.len() as u32)
+                    .bytes(value.as_bytes()),
+                Arg::Boolean(true) => self.byte(E_TRUE),
+                Arg::Boolean(false) => self.byte(E_FALSE),
+                Arg::List(values) => self.byte(E_ARR).args(values),
+            };
+        }
+        self
+    }
+
+    pub fn proofs(&mut self, proofs: &[Vec<u8>]) -> &mut Buffer {
+        self.byte(PROOFS_VERSION).size(proofs.len());
+        for proof in proofs {
+            self.array(proof);
+        }
+        self
+    }
+
     pub fn as_slice(&self) -> &[u8] {
         self.buf.as_slice()
     }
 }
+
+pub(crate) struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(buf: &'a [u8]) -> Reader<'a> {
+        Reader { buf, pos: 0 }
+    }
+
+    pub fn bytes(&mut self, n: usize) -> Result<&'a [u8], ParseError> {
+        if self.buf.len() - self.pos < n {
+            return Err(ParseError::UnexpectedEnd);
+        }
+        let bytes = &self.buf[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    pub fn peek(&self) -> Result<u8, ParseError> {
+        self.buf
+            .get(self.pos)
+            .copied()
+            .ok_or(ParseError::UnexpectedEnd)
+    }
+
+    pub fn byte(&mut self) -> Result<u8, ParseError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub fn size(&mut self) -> Result<usize, ParseError> {
+        let bytes = self.bytes(2)?;
+        Ok(((bytes[0] as usize) << 8) | bytes[1] as usize)
+    }
+
+    pub fn long(&mut self) -> Result<u64, ParseError> {
+        Ok(self
+            .bytes(8)?
+            .iter()
+            .fold(0u64, |n, b| (n << 8) | *b as u64))
+    }
+
+    pub fn int(&mut self) -> Result<u32, ParseError> {
+        Ok(self
+            .bytes(4)?
+            .iter()
+            .fold(0u32, |n, b| (n << 8) | *b as u32))
+    }
+
+    pub fn boolean(&mut self) -> Result<bool, ParseError> {
+        match self.byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            flag => Err(ParseError::InvalidFlag(flag)),
+        }
+    }
+
+    pub fn array(&mut self) -> Result<&'a [u8], ParseError> {
+        let n = self.size()?;
+        self.bytes(n)
+    }
+
+    pub fn string(&mut self) -> Result<&'a str, ParseError> {
+        std::str::from_utf8(self.array()?).map_err(|_| ParseError::InvalidUtf8)
+    }
+
+    pub fn array_opt(&mut self) -> Result<Option<&'a [u8]>, ParseError> {
+        let arr = self.array()?;
+        Ok(if arr.is_empty() { None } else { Some(arr) })
+    }
+
+    pub fn public_key(&mut self) -> Result<PublicKeyAccount, ParseError> {
+        let mut bytes = [0u8; PUBLIC_KEY_LENGTH];
+        bytes.copy_from_slice(self.bytes(PUBLIC_KEY_LENGTH)?);
+        Ok(PublicKeyAccount(bytes))
+    }
+
+    pub fn address(&mut self) -> Result<Address, ParseError> {
+        match self.peek()? {
+            1 => {
+                let mut bytes = [0u8; ADDRESS_LENGTH];
+                bytes.copy_from_slice(self.bytes(ADDRESS_LENGTH)?);
+                Ok(Address::from_array(bytes))
+            }
+            marker => Err(ParseError::UnsupportedRecipient(marker)),
+        }
+    }
+
+    pub fn recipient(&mut self) -> Result<Recipient, ParseError> {
+        match self.peek()? {
+            ALIAS_VERSION => {
+                self.byte()?;
+                let chain_id = self.byte()?;
+                let alias = Alias::new(self.string()?).map_err(|_| ParseError::InvalidAlias)?;
+                Ok(Recipient::Alias { alias, chain_id })
+            }
+            _ => Ok(Recipient::Address(self.address()?)),
+        }
+    }
+
+    pub fn hash(&mut self) -> Result<TransactionId, ParseError> {
+        let mut bytes = [0u8; HASH_LENGTH];
+        bytes.copy_from_slice(self.bytes(HASH_LENGTH)?);
+        Ok(TransactionId::new(bytes))
+    }
+
+    pub fn asset(&mut self) -> Result<Asset, ParseError> {
+        self.hash()
+    }
+
+    pub fn asset_opt(&mut self) -> Result<Option<Asset>, ParseError> {
+        if self.boolean()? {
+            Ok(Some(self.asset()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn script_opt(&mut self) -> Result<Option<&'a [u8]>, ParseError> {
+        if self.boolean()? {
+            Ok(Some(self.array()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn data_entry(&mut self) -> Result<DataEntry<'a>, ParseError> {
+        let key = self.string()?;
+        match self.byte()? {
+            0 => Ok(DataEntry::Integer(key.into(), self.long()?)),
+            1 => Ok(DataEntry::Boolean(key.into(), self.boolean()?)),
+            2 => Ok(DataEntry::Binary(key.into(), self.array()?.into())),
+            3 => Ok(DataEntry::String(key.into(), self.string()?.into())),
+            DELETE_DATA_TYPE => Ok(DataEntry::Delete(key.into())),
+            value_type => Err(ParseError::UnknownDataType(value_type)),
+        }
+    }
+
+    /// Reads a function call serialized as a RIDE expression
+    pub fn function_call(&mut self) -> Result<FunctionCall<'a>, ParseError> {
+        match self.byte()? {
+            E_FUNCALL => {}
+            tag => return Err(ParseError::UnsupportedExpression(tag)),
+        }
+        match self.byte()? {
+            FH_USER => {}
+            header => return Err(ParseError::UnsupportedExpression(header)),
+        }
+        let len = self.int()? as usize;
+        let function =
+            std::str::from_utf8(self.bytes(len)?).map_err(|_| ParseError::InvalidUtf8)?;
+        Ok(FunctionCall {
+            function: function.into(),
+            args: self.args(false)?,
+        })
+    }
+
+    /// Reads the arguments of a function call, or the values of a list argument if `in_list`.
+    /// Callable arguments can only be lists of primitive values, so a list in a list is rejected.
+    fn args(&mut self, in_list: bool) -> Result<Vec<Arg<'a>>, ParseError> {
+        let count = self.int()? as usize;
+        let mut args = Vec::new();
+        for _ in 0..count {
+            let arg = match self.byte()? {
+                E_LONG => Arg::Integer(self.long()? as i64),
+                E_BYTES => {
+                    let len = self.int()? as usize;
+                    Arg::Binary(self.bytes(len)?.into())
+                }
+                E_STRING => {
+                    let len = self.int()? as usize;
+                    let value = std::str::from_utf8(self.bytes(len)?)
+                        .map_err(|_| ParseError::InvalidUtf8)?;
+                    Arg::String(value.into())
+                }
+                E_TRUE => Arg::Boolean(true),
+                E_FALSE => Arg::Boolean(false),
+                E_ARR if !in_list => Arg::List(self.args(true)?),
+                tag => return Err(ParseError::UnsupportedExpression(tag)),
+            };
+            args.push(arg);
+        }
+        Ok(args)
+    }
+
+    pub fn proofs(&mut self) -> Result<Vec<Vec<u8>>, ParseError> {
+        match self.byte()? {
+            PROOFS_VERSION => {}
+            version => return Err(ParseError::UnsupportedProofsVersion(version)),
+        }
+        let count = self.size()?;
+        let mut proofs = Vec::with_capacity(count);
+        for _ in 0..count {
+            proofs.push(self.array()?.to_vec());
+        }
+        Ok(proofs)
+    }
+
+    pub fn finish(&self) -> Result<(), ParseError> {
+        match self.buf.len() - self.pos {
+            0 => Ok(()),
+            n => Err(ParseError::TrailingBytes(n)),
+        }
+    }
+}
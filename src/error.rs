@@ -0,0 +1,207 @@
+use crate::node::response::ResponseError;
+use crate::transaction::ParseError;
+
+use std::fmt;
+
+/// List of errors returned by the library
+#[derive(Debug)]
+pub enum Error {
+    /// The string is not valid base58
+    InvalidBase58,
+    /// The decoded value has the wrong number of bytes
+    InvalidLength { expected: usize, actual: usize },
+    /// The address version byte is not supported
+    InvalidAddressVersion(u8),
+    /// The checksum of the address does not match its content
+    InvalidChecksum,
+    /// The alias does not match the alias format
+    InvalidAlias,
+    /// The chain ID is not the expected one
+    InvalidChainId { expected: u8, actual: u8 },
+    /// A required field of a transaction builder is not set
+    MissingField(&'static str),
+    /// A field of a transaction builder has an invalid value
+    InvalidField(&'static str),
+    /// The asset is not sponsored, so fees cannot be paid in it
+    AssetNotSponsored,
+    /// The proof index is not less than the maximum number of proofs
+    InvalidProofIndex(usize),
+    /// The proof is longer than the maximum proof length
+    InvalidProofLength(usize),
+    /// The encrypted message was encrypted with another shared key
+    InvalidSharedKey,
+    /// The decrypted message does not match its authentication code
+    InvalidMessage,
+    /// The version of the encrypted message format is not supported
+    UnsupportedEncryptionVersion(u8),
+    /// The number of words of the seed phrase is not valid for the word list
+    InvalidWordCount(usize),
+    /// The word is not in the word list of the seed phrase
+    InvalidWord(String),
+    /// The checksum of the BIP39 seed phrase does not match its words
+    InvalidPhraseChecksum,
+    /// The password does not decrypt the keystore account or the encrypted seed
+    InvalidPassword,
+    /// There is no keystore account with this name
+    AccountNotFound(String),
+    /// There is already a keystore account with this name
+    AccountExists(String),
+    /// The version of the keystore format is not supported
+    UnsupportedKeystoreVersion(u32),
+    /// The transaction or order bytes could not be parsed
+    Parse(ParseError),
+    /// Reading or writing a file failed
+    Io(std::io::Error),
+    /// The request to the node failed, or the response has an error status but is not an error
+    /// of the node, e.g. a page of a reverse proxy
+    Http(reqwest::Error),
+    /// The node rejected the request with an error code and message
+    NodeApi(ResponseError),
+    /// The node response or the keystore file is not the expected JSON
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidBase58 => write!(f, "invalid base58 string"),
+            Error::InvalidLength { expected, actual } => {
+                write!(
+                    f,
+                    "invalid length: expected {} bytes, got {}",
+                    expected, actual
+                )
+            }
+            Error::InvalidAddressVersion(version) => {
+                write!(f, "invalid address version: {}", version)
+            }
+            Error::InvalidChecksum => write!(f, "invalid address checksum"),
+            Error::InvalidAlias => write!(f, "invalid alias"),
+            Error::InvalidChainId { expected, actual } => write!(
+                f,
+                "invalid chain id: expected {}, got {}",
+                *expected as char, *actual as char
+            ),
+            Error::MissingField(field) => write!(f, "missing required field `{}`", field),
+            Error::InvalidField(field) => write!(f, "invalid value of field `{}`", field),
+            Error::AssetNotSponsored => write!(f, "asset is not sponsored"),
+            Error::InvalidProofIndex(index) => write!(f, "invalid proof index: {}", index),
+            Error::InvalidProofLength(length) => write!(f, "invalid proof length: {}", length),
+            Error::InvalidSharedKey => write!(f, "invalid shared key"),
+            Error::InvalidMessage => write!(f, "invalid encrypted message"),
+            Error::UnsupportedEncryptionVersion(version) => {
+                write!(f, "unsupported encryption version: {}", version)
+            }
+            Error::InvalidWordCount(count) => write!(f, "invalid number of words: {}", count),
+            Error::InvalidWord(word) => write!(f, "invalid word `{}`", word),
+            Error::InvalidPhraseChecksum => write!(f, "invalid seed phrase checksum"),
+            Error::InvalidPassword => write!(f, "invalid password"),
+            Error::AccountNotFound(name) => write!(f, "account `{}` not found", name),
+            Error::AccountExists(name) => write!(f, "account `{}` already exists", name),
+            Error::UnsupportedKeystoreVersion(version) => {
+                write!(f, "unsupported keystore version: {}", version)
+            }
+            Error::Parse(err) => write!(f, "parse error: {}", err),
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+            Error::Http(err) => write!(f, "HTTP request failed: {}", err),
+            Error::NodeApi(err) => write!(f, "{}", err),
+            Error::Json(err) => write!(f, "invalid JSON: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(err) => Some(err),
+            Error::Io(err) => Some(err),
+            Error::Http(err) => Some(err),
+            Error::NodeApi(err) => Some(err),
+            Error::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Error {
+        Error::Parse(err)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Error {
+        Error::Http(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Error {
+        Error::Json(err)
+    }
+}
+
+impl From<ResponseError> for Error {
+    fn from(err: ResponseError) -> Error {
+        Error::NodeApi(err)
+    }
+}
+
+/// Decodes a base58 string of exactly `N` bytes
+pub(crate) fn from_base58<const N: usize>(base58: &str) -> Result<[u8; N], Error> {
+    use base58::FromBase58;
+
+    let decoded = base58.from_base58().map_err(|_| Error::InvalidBase58)?;
+    let mut bytes = [0u8; N];
+    if decoded.len() != N {
+        return Err(Error::InvalidLength {
+            expected: N,
+            actual: decoded.len(),
+        });
+    }
+    bytes.copy_from_slice(&decoded);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_base58() {
+        assert!(matches!(from_base58::<3>("Ldp"), Ok([1, 2, 3])));
+        assert!(matches!(
+            from_base58::<4>("Ldp"),
+            Err(Error::InvalidLength {
+                expected: 4,
+                actual: 3
+            })
+        ));
+        assert!(matches!(
+            from_base58::<3>("0OIl"),
+            Err(Error::InvalidBase58)
+        ));
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = Error::InvalidChainId {
+            expected: b'W',
+            actual: b'T',
+        };
+        assert_eq!(err.to_string(), "invalid chain id: expected W, got T");
+
+        let err = Error::from(ParseError::UnsupportedVersion(4, 9));
+        assert_eq!(
+            err.to_string(),
+            "parse error: unsupported version 9 of transaction type 4"
+        );
+        assert!(std::error::Error::source(&err).is_some());
+    }
+}
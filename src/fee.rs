@@ -0,0 +1,243 @@
+use crate::error::Error;
+use crate::node::Node;
+use crate::transaction::{data_payload_size, Asset, DataEntry, Transaction, TransactionData};
+
+use std::convert::TryFrom;
+
+use TransactionData::*;
+
+/// Fee unit of 0.001 WAVES in WAVELET. Minimum fees are multiples of it.
+pub const FEE_UNIT: u64 = 100_000;
+/// Extra fee of 0.004 WAVES for a transaction sent from a smart account, and for each smart asset
+/// the transaction involves
+pub const SMART_EXTRA_FEE: u64 = 400_000;
+/// Fee of issuing an asset other than an NFT: 1 WAVES
+pub const ISSUE_FEE: u64 = 100_000_000;
+
+/// Length of the proofs block of a signed legacy transaction with a single signature
+const SIGNATURE_PROOFS_LENGTH: usize = 1 + 2 + 2 + 64;
+
+/// Returns the minimum fee of the transaction in WAVELET, provided that neither the sender
+/// account nor the assets of the transaction have scripts:
+///
+/// * 1 WAVES for Issue and Set Asset Script, but 0.001 WAVES for issuing an NFT, i.e. a
+///   non-reissuable asset with the quantity of 1 and no decimals
+/// * 0.001 WAVES plus 0.0005 WAVES per transfer for Mass Transfer, rounded up to 0.001 WAVES
+/// * 0.001 WAVES per started kilobyte of data for Data
+/// * 0.003 WAVES for Exchange, 0.005 WAVES for Invoke Script, 0.01 WAVES for Set Script
+/// * 0.001 WAVES for other transactions
+///
+/// Smart accounts and smart assets add [`SMART_EXTRA_FEE`] each, see [`min_fee_with_scripts`],
+/// except for Invoke Script, which the node no longer charges the extras since feature 16.
+///
+/// # Usage
+/// ```
+/// use wavesplatform::account::{PrivateKeyAccount, TESTNET};
+/// use wavesplatform::fee;
+/// use wavesplatform::transaction::Transaction;
+/// let account = PrivateKeyAccount::from_seed("seed");
+/// let tx = Transaction::new_alias(account.public_key(), "rhino", TESTNET, 0, 1536000000000);
+/// let tx = tx.clone().with_fee(fee::min_fee(&tx));
+/// assert_eq!(tx.fee(), 100000);
+/// ```
+pub fn min_fee(tx: &Transaction) -> u64 {
+    match tx.data() {
+        Issue {
+            quantity: 1,
+            decimals: 0,
+            reissuable: false,
+            ..
+        } => FEE_UNIT,
+        Issue { .. } | SetAssetScript { .. } => ISSUE_FEE,
+        Exchange { .. } => 3 * FEE_UNIT,
+        MassTransfer { transfers, .. } => FEE_UNIT * (1 + (transfers.len() as u64).div_ceil(2)),
+        Data { data } => FEE_UNIT * (data_size(tx, data) as u64).div_ceil(1024).max(1),
+        SetScript { .. } => 10 * FEE_UNIT,
+        InvokeScript { .. } => 5 * FEE_UNIT,
+        _ => FEE_UNIT,
+    }
+}
+
+/// Returns the minimum fee of the transaction in WAVELET, including the extra fee for a smart
+/// sender account and for each asset of the transaction that is in `smart_assets`. Invoke Script
+/// transactions have no extra fee.
+pub fn min_fee_with_scripts(tx: &Transaction, smart_account: bool, smart_assets: &[Asset]) -> u64 {
+    if let InvokeScript { .. } = tx.data() {
+        return min_fee(tx);
+    }
+    let smart_asset_count = scripted_assets(tx)
+        .iter()
+        .filter(|asset| smart_assets.contains(asset))
+        .count() as u64;
+    let smart_account_count = u64::from(smart_account);
+    min_fee(tx) + SMART_EXTRA_FEE * (smart_account_count + smart_asset_count)
+}
+
+/// Returns the minimum fee of the transaction in WAVELET, looking up through the node whether the
+/// sender account has a script that requires the extra fee, and which assets of the transaction
+/// have scripts.
+/// ```no_run
+/// use wavesplatform::account::{PrivateKeyAccount, TESTNET};
+/// use wavesplatform::fee;
+/// use wavesplatform::node::{Node, TESTNET_URL};
+/// use wavesplatform::transaction::Transaction;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let node = Node::from_url(TESTNET_URL);
+///     let account = PrivateKeyAccount::from_seed("seed");
+///     let tx = Transaction::new_alias(account.public_key(), "rhino", TESTNET, 0, 1536000000000);
+///
+///     let fee = fee::min_fee_from_node(&tx, &node).await?;
+///     let signed_tx = account.sign_transaction(tx.with_fee(fee));
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn min_fee_from_node(tx: &Transaction<'_>, node: &Node<'_>) -> Result<u64, Error> {
+    if let InvokeScript { .. } = tx.data() {
+        return Ok(min_fee(tx));
+    }
+    let sender = tx.sender_public_key().to_address(tx.chain_id());
+    let smart_account = node.get_script_info(&sender.to_string()).await?.extra_fee() > 0;
+
+    let mut smart_assets = Vec::new();
+    for asset in scripted_assets(tx) {
+        if node
+            .get_assets_details(&asset.to_string())
+            .await?
+            .scripted()
+        {
+            smart_assets.push(asset);
+        }
+    }
+    Ok(min_fee_with_scripts(tx, smart_account, &smart_assets))
+}
+
+/// Converts a fee in WAVELET into the amount of a sponsored asset, given its minimum sponsored
+/// fee, i.e. the amount of the asset that is equivalent to 0.001 WAVES. The result is rounded up.
+/// ```
+/// use wavesplatform::fee;
+/// assert_eq!(fee::to_sponsored_asset(500000, 7), 35);
+/// ```
+pub fn to_sponsored_asset(fee: u64, min_sponsored_asset_fee: u64) -> u64 {
+    let amount = (u128::from(fee) * u128::from(min_sponsored_asset_fee)).div_ceil(FEE_UNIT.into());
+    u64::try_from(amount).unwrap_or(u64::MAX)
+}
+
+/// Converts a fee in WAVELET into the amount of a sponsored asset, looking up the sponsorship of
+/// the asset through the node. Fails with [`Error::AssetNotSponsored`] if the asset is not
+/// sponsored.
+pub async fn to_sponsored_asset_from_node(
+    fee: u64,
+    asset: &Asset,
+    node: &Node<'_>,
+) -> Result<u64, Error> {
+    let details = node.get_assets_details(&asset.to_string()).await?;
+    match details.min_sponsored_asset_fee() {
+        Some(min_sponsored_asset_fee) => Ok(to_sponsored_asset(fee, min_sponsored_asset_fee)),
+        None => Err(Error::AssetNotSponsored),
+    }
+}
+
+/// Size of the data the fee of a Data transaction depends on: the signed transaction bytes for
+/// the legacy version, the data entries message for protobuf versions
+fn data_size(tx: &Transaction, data: &[DataEntry]) -> usize {
+    if tx.is_protobuf() {
+        data_payload_size(data)
+    } else {
+        1 + tx.to_bytes().len() + SIGNATURE_PROOFS_LENGTH
+    }
+}
+
+/// Returns the assets whose scripts the transaction triggers
+fn scripted_assets(tx: &Transaction) -> Vec<Asset> {
+    match tx.data() {
+        Transfer { asset, .. } | MassTransfer { asset, .. } => asset.iter().copied().collect(),
+        Reissue { asset, .. } | Burn { asset, .. } | UpdateAssetInfo { asset, .. } => vec![*asset],
+        Exchange { order1, .. } => order1
+            .order
+            .amount_asset
+            .iter()
+            .chain(order1.order.price_asset.iter())
+            .copied()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::{PublicKeyAccount, TESTNET};
+    use crate::transaction::{Recipient, Version};
+
+    #[test]
+    fn test_min_fee() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let ts = 1536000000000;
+
+        let tx = Transaction::new_issue(&pk, "Token", "", 1000, 8, true, TESTNET, 0, ts, None);
+        assert_eq!(min_fee(&tx), 100000000);
+        let nft = Transaction::new_issue(&pk, "Token", "", 1, 0, false, TESTNET, 0, ts, None);
+        assert_eq!(min_fee(&nft), 100000);
+
+        let recipient = Recipient::from(pk.to_address(TESTNET));
+        let transfers = vec![(&recipient, 10); 3];
+        let tx = Transaction::new_mass_transfer(&pk, None, transfers, None, 0, ts);
+        assert_eq!(min_fee(&tx), 300000);
+        let tx = Transaction::new_mass_transfer(&pk, None, vec![], None, 0, ts);
+        assert_eq!(min_fee(&tx), 100000);
+
+        let tx = Transaction::new_script(&pk, None, TESTNET, 0, ts);
+        assert_eq!(min_fee(&tx), 1000000);
+    }
+
+    #[test]
+    fn test_data_fee() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let value = vec![0u8; 1000];
+        let entry = DataEntry::Binary("key".into(), (&value).into());
+
+        // 1000 bytes of value fit into a kilobyte of protobuf data, but not of legacy bytes
+        let tx = Transaction::new_data(&pk, vec![&entry], 0, 1536000000000);
+        assert_eq!(min_fee(&tx), 200000);
+        assert_eq!(min_fee(&tx.clone().with_version(Version::V2)), 100000);
+
+        let tx = Transaction::new_data(&pk, vec![], 0, 1536000000000);
+        assert_eq!(min_fee(&tx.with_version(Version::V2)), 100000);
+    }
+
+    #[test]
+    fn test_min_fee_with_scripts() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let asset = Asset::new([2u8; 32]);
+        let other = Asset::new([3u8; 32]);
+        let recipient = Recipient::from(pk.to_address(TESTNET));
+        let tx = Transaction::new_transfer(
+            &pk,
+            &recipient,
+            Some(&asset),
+            10,
+            None,
+            0,
+            None,
+            1536000000000,
+        );
+        assert_eq!(min_fee_with_scripts(&tx, false, &[]), 100000);
+        assert_eq!(min_fee_with_scripts(&tx, true, &[]), 500000);
+        assert_eq!(min_fee_with_scripts(&tx, false, &[other]), 100000);
+        assert_eq!(min_fee_with_scripts(&tx, true, &[asset, other]), 900000);
+
+        let tx = Transaction::new_invoke_script(&pk, &recipient, None, vec![], None, 0, 0);
+        assert_eq!(min_fee_with_scripts(&tx, true, &[asset]), 500000);
+    }
+
+    #[test]
+    fn test_to_sponsored_asset() {
+        assert_eq!(to_sponsored_asset(100000, 1), 1);
+        assert_eq!(to_sponsored_asset(500000, 7), 35);
+        assert_eq!(to_sponsored_asset(150000, 3), 5);
+        assert_eq!(to_sponsored_asset(u64::MAX, u64::MAX), u64::MAX);
+    }
+}
@@ -0,0 +1,584 @@
+mod node_wallet;
+
+use crate::account::{PrivateKeyAccount, PublicKeyAccount};
+use crate::error::Error;
+
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use aes::Aes256;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ed25519_dalek::SECRET_KEY_LENGTH;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+pub use node_wallet::*;
+
+/// Current version of the keystore file format
+pub const KEYSTORE_VERSION: u32 = 1;
+/// Number of password hashing rounds of seeds encrypted by Waves wallets
+pub const DEFAULT_ENCRYPTION_ROUNDS: u32 = 5000;
+
+const SALT_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+const KEY_LENGTH: usize = 32;
+/// Upper bounds of the scrypt parameters, so that a corrupted or crafted keystore file cannot make
+/// the key derivation exhaust the memory or run for hours
+const MAX_LOG_N: u8 = 20;
+const MAX_R: u32 = 32;
+const MAX_P: u32 = 16;
+/// Upper bound of the memory used by scrypt, `128 * r * 2^log_n` bytes
+const MAX_SCRYPT_MEMORY: u64 = 1 << 30;
+/// Header of data encrypted with the OpenSSL key derivation, followed by an 8-byte salt
+const OPENSSL_HEADER: &[u8] = b"Salted__";
+const OPENSSL_SALT_LENGTH: usize = 8;
+
+/// Parameters of the scrypt key derivation used to encrypt new keystore accounts. `log_n` is at
+/// most 20, `r` at most 32, `p` at most 16, and scrypt may use at most 1 GiB of memory
+/// (`128 * r * 2^log_n` bytes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> KdfParams {
+        KdfParams {
+            log_n: scrypt::Params::RECOMMENDED_LOG_N,
+            r: scrypt::Params::RECOMMENDED_R,
+            p: scrypt::Params::RECOMMENDED_P,
+        }
+    }
+}
+
+/// Whether a keystore account stores a seed phrase or only a private key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SecretType {
+    Seed,
+    PrivateKey,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum Kdf {
+    #[serde(rename_all = "camelCase")]
+    Scrypt {
+        salt: String,
+        log_n: u8,
+        r: u32,
+        p: u32,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum Cipher {
+    #[serde(rename = "aes-256-gcm")]
+    Aes256Gcm { nonce: String },
+}
+
+/// A named account of [`Keystore`]. The public key is stored in the clear, so that accounts can be
+/// listed without the password, the secret is encrypted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystoreEntry {
+    name: String,
+    #[serde(with = "base58_public_key")]
+    public_key: PublicKeyAccount,
+    #[serde(rename = "type")]
+    secret_type: SecretType,
+    kdf: Kdf,
+    cipher: Cipher,
+    ciphertext: String,
+}
+
+impl KeystoreEntry {
+    /// Name of the account, unique in the keystore
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Public key of the account
+    pub fn public_key(&self) -> &PublicKeyAccount {
+        &self.public_key
+    }
+
+    /// Whether the account stores a seed phrase or a private key
+    pub fn secret_type(&self) -> SecretType {
+        self.secret_type
+    }
+}
+
+/// Password-protected storage of seed phrases and private keys.
+///
+/// The keystore is a versioned JSON file with a list of named accounts. The secret of each
+/// account is encrypted with AES-256-GCM under a key derived from the password with scrypt.
+///
+/// # Usage
+/// ```no_run
+/// use wavesplatform::account::TESTNET;
+/// use wavesplatform::keystore::Keystore;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let mut keystore = Keystore::new();
+///     keystore.add_seed("main", "seed phrase", "password")?;
+///     keystore.save("keystore.json")?;
+///
+///     let keystore = Keystore::load("keystore.json")?;
+///     for entry in keystore.accounts() {
+///         println!("{}: {}", entry.name(), entry.public_key().to_address(TESTNET));
+///     }
+///     let account = keystore.unlock("main", "password")?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    version: u32,
+    accounts: Vec<KeystoreEntry>,
+    #[serde(skip)]
+    kdf_params: KdfParams,
+}
+
+impl Default for Keystore {
+    fn default() -> Keystore {
+        Keystore::new()
+    }
+}
+
+impl Keystore {
+    /// Creates an empty keystore.
+    pub fn new() -> Keystore {
+        Keystore {
+            version: KEYSTORE_VERSION,
+            accounts: Vec::new(),
+            kdf_params: KdfParams::default(),
+        }
+    }
+
+    /// Sets the key derivation parameters for the accounts added from now on. Accounts that are
+    /// already in the keystore keep their own parameters.
+    pub fn with_kdf_params(mut self, kdf_params: KdfParams) -> Keystore {
+        self.kdf_params = kdf_params;
+        self
+    }
+
+    /// Reads the keystore from a JSON file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Keystore, Error> {
+        Keystore::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Writes the keystore to a JSON file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        fs::write(path, self.to_json())?;
+        Ok(())
+    }
+
+    /// Parses the keystore from JSON. Fails if the format version is not supported.
+    pub fn from_json(json: &str) -> Result<Keystore, Error> {
+        let keystore: Keystore = serde_json::from_str(json)?;
+        if keystore.version != KEYSTORE_VERSION {
+            return Err(Error::UnsupportedKeystoreVersion(keystore.version));
+        }
+        Ok(keystore)
+    }
+
+    /// Serializes the keystore to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("keystore is serializable")
+    }
+
+    /// Lists the accounts of the keystore.
+    pub fn accounts(&self) -> &[KeystoreEntry] {
+        &self.accounts
+    }
+
+    /// Gets the account with the given name.
+    pub fn get(&self, name: &str) -> Option<&KeystoreEntry> {
+        self.accounts.iter().find(|entry| entry.name == name)
+    }
+
+    /// Encrypts a seed phrase with the password and adds it as a new account. Fails with
+    /// [`Error::AccountExists`] if there is an account with this name.
+    pub fn add_seed(
+        &mut self,
+        name: &str,
+        seed: &str,
+        password: &str,
+    ) -> Result<&KeystoreEntry, Error> {
+        let public_key = *PrivateKeyAccount::from_seed(seed).public_key();
+        self.add(
+            name,
+            public_key,
+            SecretType::Seed,
+            seed.as_bytes(),
+            password,
+        )
+    }
+
+    /// Encrypts the private key of an account with the password and adds it as a new account.
+    /// Fails with [`Error::AccountExists`] if there is an account with this name.
+    pub fn add_account(
+        &mut self,
+        name: &str,
+        account: &PrivateKeyAccount,
+        password: &str,
+    ) -> Result<&KeystoreEntry, Error> {
+        let secret = account.private_key();
+        self.add(
+            name,
+            *account.public_key(),
+            SecretType::PrivateKey,
+            &secret,
+            password,
+        )
+    }
+
+    /// Removes the account with the given name.
+    pub fn remove(&mut self, name: &str) -> Result<KeystoreEntry, Error> {
+        let index = self.index(name)?;
+        Ok(self.accounts.remove(index))
+    }
+
+    /// Decrypts the account with the given name. Fails with [`Error::InvalidPassword`] if the
+    /// password is wrong.
+    pub fn unlock(&self, name: &str, password: &str) -> Result<PrivateKeyAccount, Error> {
+        let entry = &self.accounts[self.index(name)?];
+        let secret = decrypt_entry(entry, password)?;
+        let account = match entry.secret_type {
+            SecretType::Seed => {
+                let seed = String::from_utf8(secret).map_err(|_| Error::InvalidPassword)?;
+                PrivateKeyAccount::from_seed(&seed)
+            }
+            SecretType::PrivateKey => {
+                let mut private_key = [0u8; SECRET_KEY_LENGTH];
+                if secret.len() != SECRET_KEY_LENGTH {
+                    return Err(Error::InvalidPassword);
+                }
+                private_key.copy_from_slice(&secret);
+                PrivateKeyAccount::from_private_key(private_key)
+            }
+        };
+        if *account.public_key() != entry.public_key {
+            return Err(Error::InvalidPassword);
+        }
+        Ok(account)
+    }
+
+    /// Decrypts the seed phrase of the account with the given name. Returns `None` if the account
+    /// stores only a private key.
+    pub fn unlock_seed(&self, name: &str, password: &str) -> Result<Option<String>, Error> {
+        let entry = &self.accounts[self.index(name)?];
+        if entry.secret_type != SecretType::Seed {
+            return Ok(None);
+        }
+        let seed = String::from_utf8(decrypt_entry(entry, password)?)
+            .map_err(|_| Error::InvalidPassword)?;
+        Ok(Some(seed))
+    }
+
+    fn index(&self, name: &str) -> Result<usize, Error> {
+        self.accounts
+            .iter()
+            .position(|entry| entry.name == name)
+            .ok_or_else(|| Error::AccountNotFound(name.to_owned()))
+    }
+
+    fn add(
+        &mut self,
+        name: &str,
+        public_key: PublicKeyAccount,
+        secret_type: SecretType,
+        secret: &[u8],
+        password: &str,
+    ) -> Result<&KeystoreEntry, Error> {
+        if self.get(name).is_some() {
+            return Err(Error::AccountExists(name.to_owned()));
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut salt = [0u8; SALT_LENGTH];
+        let mut nonce = [0u8; NONCE_LENGTH];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut nonce);
+
+        let KdfParams { log_n, r, p } = self.kdf_params;
+        let key = scrypt_key(password, &salt, log_n, r, p)?;
+        let ciphertext = Aes256Gcm::new(&key.into())
+            .encrypt(Nonce::from_slice(&nonce), secret)
+            .expect("secret fits into a single AES-GCM message");
+
+        self.accounts.push(KeystoreEntry {
+            name: name.to_owned(),
+            public_key,
+            secret_type,
+            kdf: Kdf::Scrypt {
+                salt: STANDARD.encode(salt),
+                log_n,
+                r,
+                p,
+            },
+            cipher: Cipher::Aes256Gcm {
+                nonce: STANDARD.encode(nonce),
+            },
+            ciphertext: STANDARD.encode(ciphertext),
+        });
+        Ok(self.accounts.last().unwrap())
+    }
+}
+
+fn decrypt_entry(entry: &KeystoreEntry, password: &str) -> Result<Vec<u8>, Error> {
+    let key = match &entry.kdf {
+        Kdf::Scrypt { salt, log_n, r, p } => {
+            scrypt_key(password, &decode_base64("salt", salt)?, *log_n, *r, *p)?
+        }
+    };
+    match &entry.cipher {
+        Cipher::Aes256Gcm { nonce } => {
+            let nonce = decode_base64("nonce", nonce)?;
+            if nonce.len() != NONCE_LENGTH {
+                return Err(Error::InvalidField("nonce"));
+            }
+            let ciphertext = decode_base64("ciphertext", &entry.ciphertext)?;
+            Aes256Gcm::new(&key.into())
+                .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+                .map_err(|_| Error::InvalidPassword)
+        }
+    }
+}
+
+fn scrypt_key(password: &str, salt: &[u8], log_n: u8, r: u32, p: u32) -> Result<[u8; 32], Error> {
+    if log_n > MAX_LOG_N
+        || r > MAX_R
+        || p > MAX_P
+        || (128 * u64::from(r)) << log_n > MAX_SCRYPT_MEMORY
+    {
+        return Err(Error::InvalidField("kdf"));
+    }
+    let params =
+        scrypt::Params::new(log_n, r, p, KEY_LENGTH).map_err(|_| Error::InvalidField("kdf"))?;
+    let mut key = [0u8; KEY_LENGTH];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+        .map_err(|_| Error::InvalidField("kdf"))?;
+    Ok(key)
+}
+
+fn decode_base64(name: &'static str, value: &str) -> Result<Vec<u8>, Error> {
+    STANDARD
+        .decode(value)
+        .map_err(|_| Error::InvalidField(name))
+}
+
+/// Encrypts a seed phrase with a password the way Waves wallets export seeds (`encryptSeed` of
+/// the Waves JS library): the password is hashed with SHA-256 `rounds` times, and the seed is
+/// encrypted with AES-256-CBC under the OpenSSL key derivation, in base64.
+///
+/// # Usage
+/// ```
+/// use wavesplatform::keystore::{decrypt_seed, encrypt_seed, DEFAULT_ENCRYPTION_ROUNDS};
+/// let encrypted = encrypt_seed("seed phrase", "password", DEFAULT_ENCRYPTION_ROUNDS);
+/// let seed = decrypt_seed(&encrypted, "password", DEFAULT_ENCRYPTION_ROUNDS).unwrap();
+/// assert_eq!(seed, "seed phrase");
+/// ```
+pub fn encrypt_seed(seed: &str, password: &str, rounds: u32) -> String {
+    let mut salt = [0u8; OPENSSL_SALT_LENGTH];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let (key, iv) = openssl_key(&strengthen_password(password, rounds), &salt);
+    let encrypted = cbc::Encryptor::<Aes256>::new(&key.into(), &iv.into())
+        .encrypt_padded_vec_mut::<Pkcs7>(seed.as_bytes());
+    STANDARD.encode([OPENSSL_HEADER, &salt, &encrypted].concat())
+}
+
+/// Decrypts a seed phrase encrypted with [`encrypt_seed`] or exported by Waves wallets. Fails
+/// with [`Error::InvalidPassword`] if the password or the number of rounds is wrong.
+pub fn decrypt_seed(encrypted: &str, password: &str, rounds: u32) -> Result<String, Error> {
+    let encrypted = decode_base64("encrypted seed", encrypted)?;
+    let header_length = OPENSSL_HEADER.len() + OPENSSL_SALT_LENGTH;
+    if encrypted.len() < header_length || !encrypted.starts_with(OPENSSL_HEADER) {
+        return Err(Error::InvalidField("encrypted seed"));
+    }
+    let (salt, encrypted) = encrypted[OPENSSL_HEADER.len()..].split_at(OPENSSL_SALT_LENGTH);
+    let (key, iv) = openssl_key(&strengthen_password(password, rounds), salt);
+    let seed = cbc::Decryptor::<Aes256>::new(&key.into(), &iv.into())
+        .decrypt_padded_vec_mut::<Pkcs7>(encrypted)
+        .map_err(|_| Error::InvalidPassword)?;
+    String::from_utf8(seed).map_err(|_| Error::InvalidPassword)
+}
+
+/// Hashes the password with SHA-256 `rounds` times, each time taking the hex string of the hash
+fn strengthen_password(password: &str, rounds: u32) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut password = password.to_owned();
+    for _ in 0..rounds {
+        password = Sha256::digest(password.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+    }
+    password
+}
+
+/// Derives the key and the IV from the passphrase and the salt with `EVP_BytesToKey` of OpenSSL
+/// with MD5 and a single iteration
+fn openssl_key(passphrase: &str, salt: &[u8]) -> ([u8; 32], [u8; 16]) {
+    use md5::{Digest, Md5};
+
+    let mut derived = Vec::with_capacity(48);
+    let mut block = Vec::new();
+    while derived.len() < 48 {
+        block = Md5::new()
+            .chain_update(&block)
+            .chain_update(passphrase)
+            .chain_update(salt)
+            .finalize()
+            .to_vec();
+        derived.extend_from_slice(&block);
+    }
+    let mut key = [0u8; 32];
+    let mut iv = [0u8; 16];
+    key.copy_from_slice(&derived[..32]);
+    iv.copy_from_slice(&derived[32..48]);
+    (key, iv)
+}
+
+mod base58_public_key {
+    use crate::account::PublicKeyAccount;
+    use crate::error::from_base58;
+
+    use base58::ToBase58;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(key: &PublicKeyAccount, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&key.to_bytes().to_base58())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<PublicKeyAccount, D::Error> {
+        let base58 = String::deserialize(d)?;
+        Ok(PublicKeyAccount(
+            from_base58(&base58).map_err(D::Error::custom)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keystore() -> Keystore {
+        Keystore::new().with_kdf_params(KdfParams {
+            log_n: 4,
+            r: 8,
+            p: 1,
+        })
+    }
+
+    #[test]
+    fn test_keystore() {
+        let mut keystore = keystore();
+        let account = PrivateKeyAccount::from_seed_with_nonce("seed", 1);
+        keystore.add_seed("main", "seed", "password").unwrap();
+        keystore.add_account("second", &account, "other").unwrap();
+        assert!(matches!(
+            keystore.add_seed("main", "seed", "password"),
+            Err(Error::AccountExists(_))
+        ));
+
+        let keystore = Keystore::from_json(&keystore.to_json()).unwrap();
+        let names: Vec<_> = keystore.accounts().iter().map(|e| e.name()).collect();
+        assert_eq!(names, ["main", "second"]);
+        assert_eq!(keystore.accounts()[1].public_key(), account.public_key());
+        assert_eq!(keystore.accounts()[1].secret_type(), SecretType::PrivateKey);
+
+        assert_eq!(
+            keystore.unlock("main", "password").unwrap(),
+            PrivateKeyAccount::from_seed("seed")
+        );
+        assert_eq!(
+            keystore.unlock_seed("main", "password").unwrap().unwrap(),
+            "seed"
+        );
+        assert_eq!(keystore.unlock("second", "other").unwrap(), account);
+        assert_eq!(keystore.unlock_seed("second", "other").unwrap(), None);
+        assert!(matches!(
+            keystore.unlock("main", "wrong"),
+            Err(Error::InvalidPassword)
+        ));
+        assert!(matches!(
+            keystore.unlock("third", "password"),
+            Err(Error::AccountNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut keystore = keystore();
+        keystore.add_seed("main", "seed", "password").unwrap();
+        assert_eq!(keystore.remove("main").unwrap().name(), "main");
+        assert!(keystore.accounts().is_empty());
+        assert!(matches!(
+            keystore.remove("main"),
+            Err(Error::AccountNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_kdf_limits() {
+        let mut keystore = keystore();
+        keystore.add_seed("main", "seed", "password").unwrap();
+        let json: serde_json::Value = serde_json::from_str(&keystore.to_json()).unwrap();
+        for (log_n, r, p) in [(40, 8, 1), (4, 33, 1), (4, 8, 17), (20, 16, 1)] {
+            let mut json = json.clone();
+            let kdf = &mut json["accounts"][0]["kdf"];
+            kdf["logN"] = log_n.into();
+            kdf["r"] = r.into();
+            kdf["p"] = p.into();
+            let keystore = Keystore::from_json(&json.to_string()).unwrap();
+            assert!(matches!(
+                keystore.unlock("main", "password"),
+                Err(Error::InvalidField("kdf"))
+            ));
+        }
+
+        let mut keystore = keystore.with_kdf_params(KdfParams {
+            log_n: 21,
+            r: 8,
+            p: 1,
+        });
+        assert!(matches!(
+            keystore.add_seed("other", "seed", "password"),
+            Err(Error::InvalidField("kdf"))
+        ));
+    }
+
+    #[test]
+    fn test_keystore_version() {
+        let json = r#"{"version": 2, "accounts": []}"#;
+        assert!(matches!(
+            Keystore::from_json(json),
+            Err(Error::UnsupportedKeystoreVersion(2))
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_seed() {
+        // encrypted with OpenSSL using the strengthened password
+        let encrypted = "U2FsdGVkX18BAgMEBQYHCPPgLeSb2hmTod52Lnu8fEE=";
+        assert_eq!(
+            decrypt_seed(encrypted, "password", DEFAULT_ENCRYPTION_ROUNDS).unwrap(),
+            "uncle push wave"
+        );
+        assert!(matches!(
+            decrypt_seed(encrypted, "wrong", DEFAULT_ENCRYPTION_ROUNDS),
+            Err(Error::InvalidPassword)
+        ));
+    }
+}
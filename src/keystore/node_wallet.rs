@@ -0,0 +1,146 @@
+use crate::account::PrivateKeyAccount;
+use crate::error::Error;
+
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockDecrypt, KeyInit};
+use aes::Aes128;
+use base58::FromBase58;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::Hmac;
+use serde::Deserialize;
+use sha2::Sha512;
+use std::fs;
+use std::path::Path;
+
+/// Salt of the key derivation of the node wallet
+const WALLET_KEY_SALT: &str = "0495c728-1614-41f6-8ac3-966c22b4a62d";
+/// Number of PBKDF2 iterations of the key derivation of the node wallet
+const WALLET_KEY_ITERATIONS: usize = 999_999;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WalletData {
+    seed: String,
+    account_seeds: Vec<String>,
+    nonce: u32,
+}
+
+/// Contents of the `wallet.dat` file of a Waves node: the wallet seed and the accounts the node
+/// has generated from it.
+///
+/// The node stores the wallet as JSON encrypted with AES-128-ECB under a key derived from the
+/// wallet password with PBKDF2-HMAC-SHA512.
+///
+/// # Usage
+/// ```no_run
+/// use wavesplatform::account::MAINNET;
+/// use wavesplatform::keystore::NodeWallet;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let wallet = NodeWallet::load("wallet/wallet.dat", "password")?;
+///     for account in wallet.accounts() {
+///         println!("{}", account.public_key().to_address(MAINNET));
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct NodeWallet {
+    seed: Vec<u8>,
+    accounts: Vec<PrivateKeyAccount>,
+    nonce: u32,
+}
+
+impl NodeWallet {
+    /// Reads and decrypts the node wallet file. Fails with [`Error::InvalidPassword`] if the
+    /// password is wrong.
+    pub fn load<P: AsRef<Path>>(path: P, password: &str) -> Result<NodeWallet, Error> {
+        NodeWallet::decrypt(&fs::read_to_string(path)?, password)
+    }
+
+    /// Decrypts the contents of the node wallet file.
+    pub fn decrypt(contents: &str, password: &str) -> Result<NodeWallet, Error> {
+        NodeWallet::decrypt_with_iterations(contents, password, WALLET_KEY_ITERATIONS)
+    }
+
+    fn decrypt_with_iterations(
+        contents: &str,
+        password: &str,
+        iterations: usize,
+    ) -> Result<NodeWallet, Error> {
+        let encrypted = STANDARD
+            .decode(contents.trim())
+            .map_err(|_| Error::InvalidField("wallet"))?;
+        let mut key = [0u8; 16];
+        pbkdf2::pbkdf2::<Hmac<Sha512>>(
+            password.as_bytes(),
+            WALLET_KEY_SALT.as_bytes(),
+            iterations,
+            &mut key,
+        );
+        let json = Aes128::new(&key.into())
+            .decrypt_padded_vec::<Pkcs7>(&encrypted)
+            .map_err(|_| Error::InvalidPassword)?;
+        let data: WalletData = serde_json::from_slice(&json).map_err(|_| Error::InvalidPassword)?;
+
+        let seed = data.seed.from_base58().map_err(|_| Error::InvalidBase58)?;
+        let accounts = data
+            .account_seeds
+            .iter()
+            .map(|account_seed| {
+                let account_seed = account_seed
+                    .from_base58()
+                    .map_err(|_| Error::InvalidBase58)?;
+                Ok(PrivateKeyAccount::from_account_seed(&account_seed))
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(NodeWallet {
+            seed,
+            accounts,
+            nonce: data.nonce,
+        })
+    }
+
+    /// Wallet seed bytes
+    pub fn seed(&self) -> &[u8] {
+        &self.seed
+    }
+
+    /// Wallet seed as a seed phrase, if the seed is a UTF-8 string
+    pub fn seed_phrase(&self) -> Option<&str> {
+        std::str::from_utf8(&self.seed).ok()
+    }
+
+    /// Accounts generated by the node, in the order of their nonces
+    pub fn accounts(&self) -> &[PrivateKeyAccount] {
+        &self.accounts
+    }
+
+    /// Nonce of the next account the node will generate
+    pub fn nonce(&self) -> u32 {
+        self.nonce
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // {"seed":"3x5dmD","accountSeeds":[...],"nonce":2} encrypted with 10 iterations
+    const WALLET: &str = "UYUufRYB60bIY5dHHVmwIrCPC4n6w0E12p/j4Aplmq0mCqILJ3UPOxgIowj0tcpBfOJG/CS8NWySF0OCEySHZOwbO7zO8FHtTo9aufWZcn8pPKWwnAWr354Os0IAVeu/jNOw2t6XPiIROb7ZfVXZHe/CKt3LQWcTFLdRFTAwxxXweMFi1tc3NX+Sl3jeFk+s";
+
+    #[test]
+    fn test_node_wallet() {
+        let wallet = NodeWallet::decrypt_with_iterations(WALLET, "password", 10).unwrap();
+        assert_eq!(wallet.seed_phrase(), Some("seed"));
+        assert_eq!(wallet.nonce(), 2);
+        let accounts: Vec<_> = PrivateKeyAccount::iter_from_seed("seed").take(2).collect();
+        assert_eq!(wallet.accounts(), accounts.as_slice());
+
+        assert!(matches!(
+            NodeWallet::decrypt_with_iterations(WALLET, "wrong", 10),
+            Err(Error::InvalidPassword)
+        ));
+    }
+}
@@ -45,6 +45,12 @@ mod bytebuffer;
 
 /// Address module
 pub mod account;
+/// Error module
+pub mod error;
+/// Module for calculating minimum transaction fees
+pub mod fee;
+/// Module for storing accounts encrypted with a password
+pub mod keystore;
 /// Module for interacting with the REST API of a Waves node
 pub mod node;
 /// Seed phrase module
@@ -1,7 +1,14 @@
 /// Module with a set of node answer struct
 pub mod response;
 
+use crate::error::Error;
+use crate::transaction::{DataEntry, ProvenTransaction};
+
+use futures_util::stream::{self, Stream};
 use response::*;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::collections::VecDeque;
 
 /// Mainnet node REST API
 pub const MAINNET_URL: &str = "https://nodes.wavesnodes.com";
@@ -51,15 +58,10 @@ impl<'a> Node<'a> {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_balance(
-        &self,
-        address: &str,
-    ) -> Result<ResponseBalance, Box<dyn std::error::Error>> {
+    pub async fn get_balance(&self, address: &str) -> Result<ResponseBalance, Error> {
         let url = format!("{}/addresses/balance/{}", self.url, address);
 
-        let res = reqwest::get(url).await?.json::<ResponseBalance>().await?;
-
-        Ok(res)
+        get(url).await
     }
 
     /// Get the available, regular, generating, and effective balance
@@ -85,15 +87,10 @@ impl<'a> Node<'a> {
     pub async fn get_balance_details(
         &self,
         address: &str,
-    ) -> Result<ResponseBalanceDetails, Box<dyn std::error::Error>> {
+    ) -> Result<ResponseBalanceDetails, Error> {
         let url = format!("{}/addresses/balance/details/{}", self.url, address);
 
-        let res = reqwest::get(url)
-            .await?
-            .json::<ResponseBalanceDetails>()
-            .await?;
-
-        Ok(res)
+        get(url).await
     }
 
     /// Get an address associated with a given alias.
@@ -111,15 +108,156 @@ impl<'a> Node<'a> {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_address_by_alias(
-        &self,
-        alias: &str,
-    ) -> Result<ResponseAddress, Box<dyn std::error::Error>> {
+    pub async fn get_address_by_alias(&self, alias: &str) -> Result<ResponseAddress, Error> {
         let url = format!("{}/alias/by-alias/{}", self.url, alias);
 
-        let res = reqwest::get(url).await?.json::<ResponseAddress>().await?;
+        get(url).await
+    }
+
+    /// Get the script of an account, its complexity and the extra fee for transactions sent from it
+    /// ```no_run
+    /// use wavesplatform::node::{Node, MAINNET_URL};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let node = Node::from_url(MAINNET_URL);
+    ///
+    ///     let result = node
+    ///         .get_script_info("3PEktVux2RhchSN63DsDo4b4mz4QqzKSeDv")
+    ///         .await?;
+    ///
+    ///     println!("Extra fee: {}", result.extra_fee());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_script_info(&self, address: &str) -> Result<ResponseScriptInfo, Error> {
+        let url = format!("{}/addresses/scriptInfo/{}", self.url, address);
+
+        get(url).await
+    }
+
+    /// Get all entries of the data storage of an account
+    /// ```no_run
+    /// use wavesplatform::node::{Node, MAINNET_URL};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let node = Node::from_url(MAINNET_URL);
+    ///
+    ///     let result = node
+    ///         .get_data("3PEktVux2RhchSN63DsDo4b4mz4QqzKSeDv")
+    ///         .await?;
+    ///
+    ///     for entry in result {
+    ///         println!("{}", entry);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_data(&self, address: &str) -> Result<Vec<DataEntry<'static>>, Error> {
+        let url = format!("{}/addresses/data/{}", self.url, address);
 
-        Ok(res)
+        get(url).await
+    }
+
+    /// Get the entries of the data storage of an account with the given keys
+    /// ```no_run
+    /// use wavesplatform::node::{Node, MAINNET_URL};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let node = Node::from_url(MAINNET_URL);
+    ///
+    ///     let result = node
+    ///         .get_data_by_keys("3PEktVux2RhchSN63DsDo4b4mz4QqzKSeDv", &["int", "bool"])
+    ///         .await?;
+    ///
+    ///     println!("{:?}", result);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_data_by_keys(
+        &self,
+        address: &str,
+        keys: &[&str],
+    ) -> Result<Vec<DataEntry<'static>>, Error> {
+        let url = format!("{}/addresses/data/{}", self.url, address);
+
+        let res = reqwest::Client::new()
+            .post(url)
+            .json(&json!({ "keys": keys }))
+            .send()
+            .await?;
+
+        parse(res).await
+    }
+
+    /// Get the entries of the data storage of an account with keys that match the regular
+    /// expression
+    /// ```no_run
+    /// use wavesplatform::node::{Node, MAINNET_URL};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let node = Node::from_url(MAINNET_URL);
+    ///
+    ///     let result = node
+    ///         .get_data_by_regex("3PEktVux2RhchSN63DsDo4b4mz4QqzKSeDv", r"^order_\w+$")
+    ///         .await?;
+    ///
+    ///     println!("{:?}", result);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_data_by_regex(
+        &self,
+        address: &str,
+        regex: &str,
+    ) -> Result<Vec<DataEntry<'static>>, Error> {
+        let url = format!(
+            "{}/addresses/data/{}?matches={}",
+            self.url,
+            address,
+            url_encode(regex)
+        );
+
+        get(url).await
+    }
+
+    /// Get the entry of the data storage of an account with the given key
+    /// ```no_run
+    /// use wavesplatform::node::{Node, MAINNET_URL};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let node = Node::from_url(MAINNET_URL);
+    ///
+    ///     let result = node
+    ///         .get_data_by_key("3PEktVux2RhchSN63DsDo4b4mz4QqzKSeDv", "int")
+    ///         .await?;
+    ///
+    ///     println!("{}", result);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_data_by_key(
+        &self,
+        address: &str,
+        key: &str,
+    ) -> Result<DataEntry<'static>, Error> {
+        let url = format!(
+            "{}/addresses/data/{}/{}",
+            self.url,
+            address,
+            url_encode(key)
+        );
+
+        get(url).await
     }
 
     /// Get detailed information about given asset
@@ -139,15 +277,102 @@ impl<'a> Node<'a> {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_assets_details(
+    pub async fn get_assets_details(&self, asset_id: &str) -> Result<ResponseAsset, Error> {
+        let url = format!("{}/assets/details/{}", self.url, asset_id);
+
+        get(url).await
+    }
+
+    /// Get the balances of all the assets held at a given address, except NFTs
+    /// ```no_run
+    /// use wavesplatform::node::{Node, MAINNET_URL};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let node = Node::from_url(MAINNET_URL);
+    ///
+    ///     let result = node
+    ///         .get_assets_balance("3PEktVux2RhchSN63DsDo4b4mz4QqzKSeDv")
+    ///         .await?;
+    ///
+    ///     for balance in result.balances() {
+    ///         println!("{}: {}", balance.asset_id(), balance.balance());
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_assets_balance(&self, address: &str) -> Result<ResponseAssetsBalance, Error> {
+        let url = format!("{}/assets/balance/{}", self.url, address);
+
+        get(url).await
+    }
+
+    /// Get the balance of a given asset at a given address
+    /// ```no_run
+    /// use wavesplatform::node::{Node, MAINNET_URL};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let node = Node::from_url(MAINNET_URL);
+    ///
+    ///     let result = node
+    ///         .get_asset_balance(
+    ///             "3PEktVux2RhchSN63DsDo4b4mz4QqzKSeDv",
+    ///             "34N9YcEETLWn93qYQ64EsP1x89tSruJU44RrEMSXXEPJ",
+    ///         )
+    ///         .await?;
+    ///
+    ///     println!("Balance: {}", result.balance());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_asset_balance(
         &self,
+        address: &str,
         asset_id: &str,
-    ) -> Result<ResponseAsset, Box<dyn std::error::Error>> {
-        let url = format!("{}/assets/details/{}", self.url, asset_id);
+    ) -> Result<ResponseAssetBalance, Error> {
+        let url = format!("{}/assets/balance/{}/{}", self.url, address, asset_id);
 
-        let res = reqwest::get(url).await?.json::<ResponseAsset>().await?;
+        get(url).await
+    }
 
-        Ok(res)
+    /// Get the NFTs held at an address as a stream that requests pages of `page_size` NFTs (at
+    /// most 1000) one by one, passing the ID of the last NFT of a page as the `after` cursor of the
+    /// next request
+    ///
+    /// A failed request ends the stream after its error.
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use wavesplatform::node::{Node, MAINNET_URL};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let node = Node::from_url(MAINNET_URL);
+    ///
+    ///     let mut nfts = node.get_nfts("3PEktVux2RhchSN63DsDo4b4mz4QqzKSeDv", 100);
+    ///     while let Some(nft) = nfts.next().await {
+    ///         println!("{}", nft?.name());
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn get_nfts(
+        &self,
+        address: &str,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<ResponseAsset, Error>> + Unpin {
+        paginate(Pages {
+            url: format!("{}/assets/nft/{}/limit/{}", self.url, address, page_size),
+            nested: false,
+            cursor: "assetId",
+            page_size: page_size as usize,
+            after: None,
+            page: VecDeque::new(),
+            last_page: false,
+        })
     }
 
     /// Get headers of a given block
@@ -167,15 +392,10 @@ impl<'a> Node<'a> {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_blocks_headers(
-        &self,
-        id: &str,
-    ) -> Result<ResponseBlock, Box<dyn std::error::Error>> {
+    pub async fn get_blocks_headers(&self, id: &str) -> Result<ResponseBlock, Error> {
         let url = format!("{}/blocks/headers/{}", self.url, id);
 
-        let res = reqwest::get(url).await?.json::<ResponseBlock>().await?;
-
-        Ok(res)
+        get(url).await
     }
 
     /// Get headers of a given block
@@ -193,15 +413,10 @@ impl<'a> Node<'a> {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_blocks_headers_at_height(
-        &self,
-        height: u64,
-    ) -> Result<ResponseBlock, Box<dyn std::error::Error>> {
+    pub async fn get_blocks_headers_at_height(&self, height: u64) -> Result<ResponseBlock, Error> {
         let url = format!("{}/blocks/headers/at/{}", self.url, height);
 
-        let res = reqwest::get(url).await?.json::<ResponseBlock>().await?;
-
-        Ok(res)
+        get(url).await
     }
 
     /// Get the block at the current blockchain height
@@ -219,12 +434,10 @@ impl<'a> Node<'a> {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_blocks_last(&self) -> Result<ResponseBlock, Box<dyn std::error::Error>> {
+    pub async fn get_blocks_last(&self) -> Result<ResponseBlock, Error> {
         let url = format!("{}/blocks/last", self.url);
 
-        let res = reqwest::get(url).await?.json::<ResponseBlock>().await?;
-
-        Ok(res)
+        get(url).await
     }
 
     /// Get lease parameters by lease ID
@@ -244,15 +457,10 @@ impl<'a> Node<'a> {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_leasing_info(
-        &self,
-        id: &str,
-    ) -> Result<ResponseLease, Box<dyn std::error::Error>> {
+    pub async fn get_leasing_info(&self, id: &str) -> Result<ResponseLease, Error> {
         let url = format!("{}/leasing/info/{}", self.url, id);
 
-        let res = reqwest::get(url).await?.json::<ResponseLease>().await?;
-
-        Ok(res)
+        get(url).await
     }
 
     /// Get node version
@@ -270,17 +478,10 @@ impl<'a> Node<'a> {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_node_version(
-        &self,
-    ) -> Result<ResponseNodeVersion, Box<dyn std::error::Error>> {
+    pub async fn get_node_version(&self) -> Result<ResponseNodeVersion, Error> {
         let url = format!("{}/node/version", self.url);
 
-        let res = reqwest::get(url)
-            .await?
-            .json::<ResponseNodeVersion>()
-            .await?;
-
-        Ok(res)
+        get(url).await
     }
 
     /// Get a transaction by its ID
@@ -300,18 +501,10 @@ impl<'a> Node<'a> {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_transactions_info(
-        &self,
-        id: &str,
-    ) -> Result<ResponseTransaction, Box<dyn std::error::Error>> {
+    pub async fn get_transactions_info(&self, id: &str) -> Result<ResponseTransaction, Error> {
         let url = format!("{}/transactions/info/{}", self.url, id);
 
-        let res = reqwest::get(url)
-            .await?
-            .json::<ResponseTransaction>()
-            .await?;
-
-        Ok(res)
+        get(url).await
     }
 
     /// Get transaction status by its ID
@@ -334,14 +527,222 @@ impl<'a> Node<'a> {
     pub async fn get_transactions_status(
         &self,
         id: &str,
-    ) -> Result<ResponseTransactionStatus, Box<dyn std::error::Error>> {
+    ) -> Result<ResponseTransactionStatus, Error> {
         let url = format!("{}/transactions/status/{}", self.url, id);
 
-        let res = reqwest::get(url)
-            .await?
-            .json::<ResponseTransactionStatus>()
-            .await?;
+        get(url).await
+    }
+
+    /// Get the transactions of an address, newest first, as a stream that requests pages of
+    /// `page_size` transactions (at most 1000) one by one, passing the ID of the last transaction
+    /// of a page as the `after` cursor of the next request
+    ///
+    /// A transaction that cannot be parsed, e.g. of a type this library does not support, is
+    /// yielded as [`ResponseProvenTransaction::Unsupported`] with its JSON. A failed request ends
+    /// the stream after its error.
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use wavesplatform::node::response::ResponseProvenTransaction;
+    /// use wavesplatform::node::{Node, MAINNET_URL};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let node = Node::from_url(MAINNET_URL);
+    ///
+    ///     let mut txs = node.get_transactions_by_address("3PEktVux2RhchSN63DsDo4b4mz4QqzKSeDv", 100);
+    ///     while let Some(tx) = txs.next().await {
+    ///         match tx? {
+    ///             ResponseProvenTransaction::Parsed(tx) => println!("{:?}", tx),
+    ///             ResponseProvenTransaction::Unsupported(json) => println!("{}", json),
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn get_transactions_by_address(
+        &self,
+        address: &str,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<ResponseProvenTransaction, Error>> + Unpin {
+        paginate(Pages {
+            url: format!(
+                "{}/transactions/address/{}/limit/{}",
+                self.url, address, page_size
+            ),
+            // the node wraps the page of transactions into another array
+            nested: true,
+            cursor: "id",
+            page_size: page_size as usize,
+            after: None,
+            page: VecDeque::new(),
+            last_page: false,
+        })
+    }
+
+    /// Broadcast a signed transaction to the blockchain
+    ///
+    /// If the node rejects the transaction, the returned error is [`Error::NodeApi`] with the
+    /// node's error code and message. Other error responses, e.g. of an unreachable node behind a
+    /// reverse proxy, are [`Error::Http`] with the HTTP status.
+    /// ```no_run
+    /// use wavesplatform::account::{PrivateKeyAccount, TESTNET};
+    /// use wavesplatform::node::{Node, TESTNET_URL};
+    /// use wavesplatform::transaction::Transaction;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let node = Node::from_url(TESTNET_URL);
+    ///
+    ///     let account = PrivateKeyAccount::from_seed("seed");
+    ///     let tx = Transaction::new_alias(account.public_key(), "rhino", TESTNET, 100000, 1536000000000);
+    ///     let signed_tx = account.sign_transaction(tx);
+    ///
+    ///     let result = node.broadcast(&signed_tx).await?;
+    ///
+    ///     println!("{:?}", result);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn broadcast(&self, tx: &ProvenTransaction<'_>) -> Result<ResponseBroadcast, Error> {
+        let url = format!("{}/transactions/broadcast", self.url);
+
+        let res = reqwest::Client::new().post(url).json(tx).send().await?;
+
+        parse(res).await
+    }
+}
+
+/// State of a stream of items that are requested page by page
+struct Pages {
+    url: String,
+    /// Whether the node wraps the page into another array
+    nested: bool,
+    /// Field of the last item of a page to request the next page after
+    cursor: &'static str,
+    page_size: usize,
+    after: Option<String>,
+    page: VecDeque<Value>,
+    last_page: bool,
+}
+
+impl Pages {
+    async fn next_page(&mut self) -> Result<(), Error> {
+        let url = match &self.after {
+            Some(after) => format!("{}?after={}", self.url, after),
+            None => self.url.clone(),
+        };
+        self.page = if self.nested {
+            let pages: Vec<VecDeque<Value>> = get(url).await?;
+            pages.into_iter().next().unwrap_or_default()
+        } else {
+            get(url).await?
+        };
+
+        self.after = self
+            .page
+            .back()
+            .and_then(|item| item[self.cursor].as_str())
+            .map(String::from);
+        self.last_page = self.page.len() < self.page_size || self.after.is_none();
+        Ok(())
+    }
+}
+
+/// Streams the items of all the pages. Items that fail to parse are yielded as errors, and a
+/// failed request ends the stream.
+fn paginate<T: DeserializeOwned>(pages: Pages) -> impl Stream<Item = Result<T, Error>> + Unpin {
+    Box::pin(stream::unfold(pages, |mut state| async move {
+        loop {
+            if let Some(item) = state.page.pop_front() {
+                let item = serde_json::from_value(item).map_err(Error::from);
+                return Some((item, state));
+            }
+            if state.last_page {
+                return None;
+            }
+            if let Err(err) = state.next_page().await {
+                state.last_page = true;
+                return Some((Err(err), state));
+            }
+        }
+    }))
+}
+
+async fn get<T: DeserializeOwned>(url: String) -> Result<T, Error> {
+    parse(reqwest::get(url).await?).await
+}
+
+async fn parse<T: DeserializeOwned>(res: reqwest::Response) -> Result<T, Error> {
+    let status_error = res.error_for_status_ref().err();
+    let body = res.bytes().await?;
+
+    match status_error {
+        None => Ok(serde_json::from_slice(&body)?),
+        // errors that are not the node's own, e.g. the HTML page of a reverse proxy or an empty
+        // 404, keep the HTTP status
+        Some(err) => Err(match serde_json::from_slice(&body) {
+            Ok(response) => Error::NodeApi(response),
+            Err(_) => Error::Http(err),
+        }),
+    }
+}
+
+/// Percent-encodes everything but the unreserved characters of URLs
+fn url_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, body: &'static str) -> reqwest::Response {
+        http::Response::builder()
+            .status(status)
+            .body(body)
+            .unwrap()
+            .into()
+    }
+
+    #[tokio::test]
+    async fn test_parse_errors() {
+        let res = response(400, r#"{"error": 112, "message": "State check failed"}"#);
+        match parse::<ResponseBalance>(res).await {
+            Err(Error::NodeApi(err)) => assert_eq!(err.error(), 112),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let res = response(502, "<html><body>502 Bad Gateway</body></html>");
+        match parse::<ResponseBalance>(res).await {
+            Err(Error::Http(err)) => assert_eq!(err.status().unwrap().as_u16(), 502),
+            other => panic!("unexpected {:?}", other),
+        }
+        let res = response(404, "");
+        match parse::<ResponseBalance>(res).await {
+            Err(Error::Http(err)) => assert_eq!(err.status().unwrap().as_u16(), 404),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let res = response(200, "<html></html>");
+        assert!(matches!(
+            parse::<ResponseBalance>(res).await,
+            Err(Error::Json(_))
+        ));
+    }
 
-        Ok(res)
+    #[test]
+    fn test_url_encode() {
+        assert_eq!(url_encode("order_1"), "order_1");
+        assert_eq!(url_encode(r"^a\w+ é"), "%5Ea%5Cw%2B%20%C3%A9");
     }
 }
@@ -1,4 +1,8 @@
-use serde::Deserialize;
+use crate::transaction::{Asset, ProvenTransaction, TransactionId};
+
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::fmt;
 
 macro_rules! response_generator {
     (struct $name:ident {
@@ -7,7 +11,7 @@ macro_rules! response_generator {
             $field_name:ident: $field_type:ty,
         )*
     }) => {
-        #[derive(Debug, Deserialize)]
+        #[derive(Debug, Clone, Deserialize)]
         #[serde(rename_all = "camelCase")]
         pub struct $name {
             $(
@@ -49,12 +53,52 @@ response_generator! {
 
 response_generator! {
     struct ResponseAsset {
-        asset_id: String,
+        asset_id: Asset,
         name: String,
         description: String,
         decimals: u64,
         issuer: String,
         reissuable: bool,
+        quantity: u64,
+        scripted: bool,
+        min_sponsored_asset_fee: Option<u64>,
+        origin_transaction_id: TransactionId,
+    }
+}
+
+response_generator! {
+    struct ResponseAssetBalance {
+        address: String,
+        asset_id: Asset,
+        balance: u64,
+    }
+}
+
+response_generator! {
+    struct ResponseAssetsBalance {
+        address: String,
+        balances: Vec<ResponseAssetBalanceDetails>,
+    }
+}
+
+response_generator! {
+    struct ResponseAssetBalanceDetails {
+        asset_id: Asset,
+        balance: u64,
+        reissuable: bool,
+        quantity: u64,
+        min_sponsored_asset_fee: Option<u64>,
+        sponsor_balance: Option<u64>,
+        issue_transaction: Option<ResponseProvenTransaction>,
+    }
+}
+
+response_generator! {
+    struct ResponseScriptInfo {
+        address: String,
+        script: Option<String>,
+        complexity: u64,
+        extra_fee: u64,
     }
 }
 
@@ -114,3 +158,374 @@ response_generator! {
         confirmations: u64,
     }
 }
+
+response_generator! {
+    struct ResponseBroadcast {
+        #[serde(alias = "type")]
+        type_id: u64,
+        version: u64,
+        id: String,
+        sender: String,
+        sender_public_key: String,
+        fee: u64,
+        fee_asset_id: Option<String>,
+        timestamp: u64,
+        proofs: Vec<String>,
+    }
+}
+
+response_generator! {
+    struct ResponseError {
+        error: u64,
+        message: String,
+    }
+}
+
+/// A signed transaction returned by the node, either parsed or, if this library cannot parse it,
+/// as the raw JSON object
+#[derive(Debug, Clone)]
+pub enum ResponseProvenTransaction {
+    Parsed(Box<ProvenTransaction<'static>>),
+    /// A transaction of a type or version this library does not support, e.g. a Genesis,
+    /// Payment or Ethereum transaction
+    Unsupported(Value),
+}
+
+impl ResponseProvenTransaction {
+    /// Returns the parsed transaction, or `None` if it is not supported
+    pub fn parsed(&self) -> Option<&ProvenTransaction<'static>> {
+        match self {
+            ResponseProvenTransaction::Parsed(tx) => Some(tx.as_ref()),
+            ResponseProvenTransaction::Unsupported(_) => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ResponseProvenTransaction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = Value::deserialize(deserializer)?;
+        Ok(match ProvenTransaction::deserialize(&json) {
+            Ok(tx) => ResponseProvenTransaction::Parsed(Box::new(tx)),
+            Err(_) => ResponseProvenTransaction::Unsupported(json),
+        })
+    }
+}
+
+impl fmt::Display for ResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Node error {}: {}", self.error, self.message)
+    }
+}
+
+impl std::error::Error for ResponseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::{PrivateKeyAccount, TESTNET};
+    use crate::transaction::{Transaction, Version};
+
+    use serde_json::json;
+
+    #[test]
+    fn test_assets_balance() {
+        let json = r#"{
+            "address": "3Mq3pueXcAgLcuWvJzJ4ndRHfqYgjUZvL7q",
+            "balances": [
+                {
+                    "assetId": "85gPhjumNgwaMUpGfx9jEQqJMorbEjTQ4EUAHwfoYKjd",
+                    "reissuable": false,
+                    "minSponsoredAssetFee": null,
+                    "sponsorBalance": null,
+                    "quantity": 32,
+                    "issueTransaction": {
+                        "type": 3,
+                        "id": "85gPhjumNgwaMUpGfx9jEQqJMorbEjTQ4EUAHwfoYKjd",
+                        "fee": 100000000,
+                        "feeAssetId": null,
+                        "timestamp": 1662305845939,
+                        "version": 3,
+                        "chainId": 84,
+                        "sender": "3Mq3pueXcAgLcuWvJzJ4ndRHfqYgjUZvL7q",
+                        "senderPublicKey": "8jDzNuHZwuTTo6WvZMdSoNc8ydY6a7UnxvwHZ8kooMuS",
+                        "proofs": [
+                            "2ffFqpGnJeNrKHcGNNp4DBx36HwNNAYPmDozY4smS3CXAnuACMSNvmeWSW3RMLVTvtz8bVEN527hWrhCZXZC73Tw"
+                        ],
+                        "assetId": "85gPhjumNgwaMUpGfx9jEQqJMorbEjTQ4EUAHwfoYKjd",
+                        "name": "test asset",
+                        "quantity": 32,
+                        "reissuable": false,
+                        "decimals": 3,
+                        "description": "this is test asset",
+                        "script": null
+                    },
+                    "balance": 42
+                },
+                {
+                    "assetId": "GyH2wqKQcjHtz6KgkUNzUpDYYy1azqZdYHZ2awXHWqYx",
+                    "reissuable": false,
+                    "minSponsoredAssetFee": 1,
+                    "sponsorBalance": 199900003,
+                    "quantity": 2,
+                    "issueTransaction": null,
+                    "balance": 2
+                }
+            ]
+        }"#;
+        let response: ResponseAssetsBalance = serde_json::from_str(json).unwrap();
+        let balances = response.balances();
+        assert_eq!(balances.len(), 2);
+
+        let asset = Asset::from_string("85gPhjumNgwaMUpGfx9jEQqJMorbEjTQ4EUAHwfoYKjd").unwrap();
+        assert_eq!(balances[0].asset_id(), asset);
+        assert_eq!(balances[0].balance(), 42);
+        assert_eq!(balances[0].sponsor_balance(), None);
+        let issue = balances[0].issue_transaction().unwrap();
+        assert_eq!(issue.parsed().unwrap().tx.id(), asset);
+
+        assert_eq!(balances[1].min_sponsored_asset_fee(), Some(1));
+        assert_eq!(balances[1].sponsor_balance(), Some(199900003));
+        assert!(balances[1].issue_transaction().is_none());
+    }
+
+    #[test]
+    fn test_assets_balance_issue_versions() {
+        let account = PrivateKeyAccount::from_seed("test");
+        let pk = account.public_key();
+        let issue = Transaction::new_issue(
+            pk,
+            "coin",
+            "coin",
+            100000000,
+            8,
+            false,
+            TESTNET,
+            100000000,
+            1536000000000,
+            None,
+        )
+        .with_version(Version::V1);
+        let issue = account.sign_transaction(issue);
+        let mut issue_json = serde_json::to_value(&issue).unwrap();
+        // the node returns version 1 transactions with a null chain ID
+        issue_json["chainId"] = Value::Null;
+        issue_json["sender"] = json!(pk.to_address(TESTNET).to_string());
+        let mut unknown_json = issue_json.clone();
+        unknown_json["version"] = json!(0);
+
+        let balance = |issue_json: &Value| {
+            json!({
+                "assetId": issue.tx.id().to_string(),
+                "reissuable": false,
+                "minSponsoredAssetFee": null,
+                "sponsorBalance": null,
+                "quantity": 100000000,
+                "issueTransaction": issue_json,
+                "balance": 100000000
+            })
+        };
+        let json = json!({
+            "address": pk.to_address(TESTNET).to_string(),
+            "balances": [balance(&issue_json), balance(&unknown_json)]
+        });
+        let response: ResponseAssetsBalance = serde_json::from_value(json).unwrap();
+        let balances = response.balances();
+        let parsed = balances[0].issue_transaction().unwrap();
+        assert_eq!(parsed.parsed(), Some(&issue));
+        assert_eq!(parsed.parsed().unwrap().tx.id(), balances[0].asset_id());
+        // an issue transaction this library cannot parse does not fail the whole response
+        match balances[1].issue_transaction().unwrap() {
+            ResponseProvenTransaction::Unsupported(raw) => assert_eq!(raw, unknown_json),
+            parsed => panic!("unexpected {:?}", parsed),
+        }
+    }
+
+    #[test]
+    fn test_transactions_page() {
+        // transactions returned by Testnet, Stagenet and Mainnet nodes, including Genesis, Payment
+        // and Ethereum transactions that this library does not support
+        let json = r#"[
+            [
+                {
+                    "type": 1,
+                    "id": "3zpi4i5SeCoaiCBn1iuTUvCc5aahvtabqXBTrCXy1Y3ujUbJo56VVv6n4HQtcwiFapvg3BKV6stb5QkxsBrudTKZ",
+                    "fee": 0,
+                    "timestamp": 1478000000000,
+                    "signature": "3zpi4i5SeCoaiCBn1iuTUvCc5aahvtabqXBTrCXy1Y3ujUbJo56VVv6n4HQtcwiFapvg3BKV6stb5QkxsBrudTKZ",
+                    "recipient": "3NBVqYXrapgJP9atQccdBPAgJPwHDKkh6A8",
+                    "amount": 200000000000000
+                },
+                {
+                    "type": 2,
+                    "id": "3MBsS7S42PVEM8c1XxLsGsxzhitPsyaazDs1QoE26pCTHdRMYRv7n984wmjSFP863iZ2GR28aunSVvPC8sooEpbP",
+                    "fee": 1,
+                    "feeAssetId": null,
+                    "timestamp": 1465747778592,
+                    "sender": "3PAWwWa6GbwcJaFzwqXQN5KQm7H96Y7SHTQ",
+                    "senderPublicKey": "7LBopaBdBzQbgqrnwgmgCDhcSTb32MYhE96SnSHcqZC2",
+                    "proofs": [
+                        "3MBsS7S42PVEM8c1XxLsGsxzhitPsyaazDs1QoE26pCTHdRMYRv7n984wmjSFP863iZ2GR28aunSVvPC8sooEpbP"
+                    ],
+                    "recipient": "3PP4hNGAJaMqmx9vpdYUHk8owF3mwbUevoz",
+                    "amount": 910924657498
+                },
+                {
+                    "type": 4,
+                    "id": "DBozd2VWYe1FDkrdQnJgvcxh9B6mL872onqpSCjF4a7t",
+                    "fee": 100000,
+                    "feeAssetId": null,
+                    "timestamp": 1662972278752,
+                    "version": 3,
+                    "chainId": 84,
+                    "sender": "3NBE5tjbQn9BHczjD6NSSuFDKVHKsBRzTv9",
+                    "senderPublicKey": "8JEFTsZfqp2Y7HpmaxqgGtiMLfsNAAq3bMkwZwGpUWPV",
+                    "proofs": [
+                        "2WDiEfTLUZwcpicqKE46UMTn2PS2Z7hVKGGZL26NsbZDH1ELyhrWh5vrpfJ37o42HomK7A2748zFmDWX3xe7kJji"
+                    ],
+                    "recipient": "3N4x4ML4D6fiU18Tpw86puRoN78FCTs9VQu",
+                    "assetId": null,
+                    "feeAsset": null,
+                    "amount": 10000,
+                    "attachment": "",
+                    "applicationStatus": "succeeded"
+                },
+                {
+                    "type": 8,
+                    "id": "FL9juc4i2e5L2LnnrcagWQf7LYBmcJrxrxQdBrxNkwjx",
+                    "fee": 100000,
+                    "feeAssetId": null,
+                    "timestamp": 1662829781999,
+                    "version": 2,
+                    "sender": "3MrmhLagifesiDUvxqsK6oW9tHEQVKzs6JJ",
+                    "senderPublicKey": "JBGeneKgUJgvPVEGddZgdKfStLvHqUU5xtJs5WhrtDrD",
+                    "proofs": [
+                        "5JFFmM1DKYyiMW35GPasuRjYNmn4aMnE5NmqgPtmN1TZfPzAYGeDg663kT6HCzxUz5yVpgocisgroTbgPv7nJB8Q"
+                    ],
+                    "amount": 100000000,
+                    "recipient": "3Mvr7snJsF3F6QWC9AzxuFPfaftELDHGHXh",
+                    "applicationStatus": "succeeded"
+                },
+                {
+                    "type": 16,
+                    "id": "7QT8tS7eC3Krzc65GVBdzGyfeCk8kDy9y2BTp6fMr6vx",
+                    "fee": 500000,
+                    "feeAssetId": null,
+                    "timestamp": 1662969274915,
+                    "version": 1,
+                    "sender": "3MuhGCajV9HXunkyuQpwXvHTjTLaMy93g9Y",
+                    "senderPublicKey": "57C4SttrQ3a2s6nHqTyPoKo6g7JFKhvojLkS3qgrVqyv",
+                    "proofs": [
+                        "5A473ZfYCnVdTvPC3n3A7AvfwpSe9SVJpEhYK3iCu2L891qVCaLWktsSohrtVmEUWE8XYth5hvdnrurCWKA4ajAc"
+                    ],
+                    "dApp": "3N4NS7d4Jo9a6F14LiFUKKYVdUkkf2eP4Zx",
+                    "payment": [],
+                    "call": {
+                        "function": "finalizeCurrentPrice",
+                        "args": [
+                            {
+                                "type": "integer",
+                                "value": 6500000
+                            },
+                            {
+                                "type": "binary",
+                                "value": "base64:lz0Lu+Wy5JmPRlBDzid1fwvf7SdK2cg3TG3GxaizEc/rf57FDCMI8qvrE0teyQGNjj1NlhGNrE98c3LSxP6WAA=="
+                            },
+                            {
+                                "type": "integer",
+                                "value": 6500000
+                            },
+                            {
+                                "type": "binary",
+                                "value": "base64:BEhxvA2BlxOm5MwYAgss1S8JrYp6O0WX4Xu/XoqVQEZOBf2SFNv//wTTo9DO2jNN+PIo3ostYSTjMABcjmi1BQ=="
+                            },
+                            {
+                                "type": "integer",
+                                "value": 6500000
+                            },
+                            {
+                                "type": "binary",
+                                "value": "base64:+ZbMzkrfzSbcdCPcOfh4/0tINICD//ZfR8EHI6CA/XMlK7UULA5yE0g2uhfqP+ffJTs6nA5ocs6OtpFUyQm3DA=="
+                            },
+                            {
+                                "type": "integer",
+                                "value": 6500000
+                            },
+                            {
+                                "type": "binary",
+                                "value": "base64:YZC++iETjT6r8wsePUm+g8AF45gZGLAJ/zhf0i6nymQs34m9xCjca9FClgBAmntjCP+zDWORF3YnygnBRvj2Dw=="
+                            },
+                            {
+                                "type": "integer",
+                                "value": 6500000
+                            },
+                            {
+                                "type": "binary",
+                                "value": "base64:A/cEQGJmlCckydAF8k4pslR2CCmRSQaJ5Ut5RF6YELVL/IZyp4G02BX2KECn7JEGvauGHsriwAW4v5rukkNpDQ=="
+                            }
+                        ]
+                    },
+                    "applicationStatus": "succeeded"
+                },
+                {
+                    "type": 18,
+                    "id": "CB6ha67dKnjT4Kz9UQvuuyzL9d3pvXkVT6GntD8Q3GsH",
+                    "fee": 100000,
+                    "feeAssetId": null,
+                    "timestamp": 1661335349747,
+                    "version": 1,
+                    "chainId": 83,
+                    "bytes": "0xf874860182cf4da9f38502540be400830186a094379d106fc58416c2b0f090b81b89371ca2d45a0788016345785d8a00008081c9a02ecee551acc6be0ec939d5d2849041c1efca2380063663cf8b0377d42adf6f15a06d573079afbae4dda60e8797ab2aa05a089dc0c823640f5cc6c409109198f2a4",
+                    "sender": "3MiKAyPxv5ccsFToCQiazxvBn4SMxECaFkU",
+                    "senderPublicKey": "5BcWEhVZFuvMhs8DRF1C8GLSbXvobkfaaRvANa86MboLSQLxC5989Zgo3Djp8WwWKo3JshYP39NnBAJRqTCXZ5Qd",
+                    "applicationStatus": "succeeded"
+                }
+            ]
+        ]"#;
+        let pages: Vec<Vec<ResponseProvenTransaction>> = serde_json::from_str(json).unwrap();
+        let json: Vec<Vec<Value>> = serde_json::from_str(json).unwrap();
+        assert_eq!(pages[0].len(), 6);
+        for (tx, json) in pages[0].iter().zip(&json[0]) {
+            match tx {
+                ResponseProvenTransaction::Parsed(tx) => {
+                    assert_eq!(tx.tx.id().to_string(), json["id"].as_str().unwrap());
+                    assert_eq!(tx.verify(), vec![0]);
+                }
+                ResponseProvenTransaction::Unsupported(raw) => {
+                    assert!([1, 2, 18].contains(&json["type"].as_u64().unwrap()));
+                    assert_eq!(raw, json);
+                }
+            }
+        }
+        let parsed: Vec<u8> = pages[0]
+            .iter()
+            .filter_map(|tx| tx.parsed().map(|tx| tx.tx.type_id()))
+            .collect();
+        assert_eq!(parsed, vec![4, 8, 16]);
+    }
+
+    #[test]
+    fn test_asset() {
+        let json = r#"{
+            "assetId": "CVwsbXjXmdYF2q4RCPuQKf7sLGpzhk7BNnYsxGZZJMym",
+            "issueHeight": 2221593,
+            "issueTimestamp": 1662728397110,
+            "issuer": "3Ms6jp75u5qnfmAgWpxbt9xHv7znBp7RHnq",
+            "issuerPublicKey": "ASA4fMdz5FirDREfB34PPi67QxLHMt8tvzRQDT64juiM",
+            "name": "AssetWithScript",
+            "description": "",
+            "decimals": 0,
+            "reissuable": true,
+            "quantity": 10000,
+            "scripted": true,
+            "minSponsoredAssetFee": null,
+            "originTransactionId": "CVwsbXjXmdYF2q4RCPuQKf7sLGpzhk7BNnYsxGZZJMym"
+        }"#;
+        let asset: ResponseAsset = serde_json::from_str(json).unwrap();
+        assert_eq!(asset.asset_id(), asset.origin_transaction_id());
+        assert_eq!(asset.quantity(), 10000);
+        assert!(asset.scripted());
+
+        let json = json.replace("CVwsbXjXmdYF2q4RCPuQKf7sLGpzhk7BNnYsxGZZJMym", "invalid");
+        assert!(serde_json::from_str::<ResponseAsset>(&json).is_err());
+    }
+}
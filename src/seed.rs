@@ -1,4 +1,35 @@
-use bip39::{Language, Mnemonic, MnemonicType};
+use crate::error::Error;
+
+use bip39::{ErrorKind, Mnemonic, MnemonicType};
+use rand::Rng;
+
+pub use bip39::Language;
+
+/// Number of words of seed phrases generated by Waves wallets
+pub const WAVES_WORD_COUNT: usize = 15;
+
+/// All BIP39 languages, English first
+const LANGUAGES: [Language; 8] = [
+    Language::English,
+    Language::ChineseSimplified,
+    Language::ChineseTraditional,
+    Language::French,
+    Language::Italian,
+    Language::Japanese,
+    Language::Korean,
+    Language::Spanish,
+];
+
+/// Dictionary of seed phrase words
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WordList {
+    /// BIP39 word list of the language. Phrases have 12, 15, 18, 21 or 24 words and end with a
+    /// checksum.
+    Bip39(Language),
+    /// Legacy Waves dictionary: the 2048 English BIP39 words picked independently at random,
+    /// without a checksum. Waves wallets generate phrases of 15 such words.
+    Waves,
+}
 
 /// Seed phrase generation function
 ///
@@ -22,14 +53,254 @@ pub fn generate_phrase() -> String {
     phrase.to_string()
 }
 
+/// Generates a seed phrase of `word_count` words from the word list. BIP39 phrases can have 12,
+/// 15, 18, 21 or 24 words, legacy Waves phrases any positive number of words.
+///
+/// # Usage
+/// ```
+/// use wavesplatform::seed::*;
+/// let phrase = generate_phrase_with(WAVES_WORD_COUNT, WordList::Waves).unwrap();
+/// assert_eq!(phrase.split(' ').count(), 15);
+///
+/// let phrase = generate_phrase_with(24, WordList::Bip39(Language::French)).unwrap();
+/// assert!(validate_phrase(&phrase, WordList::Bip39(Language::French)).is_ok());
+/// ```
+pub fn generate_phrase_with(word_count: usize, word_list: WordList) -> Result<String, Error> {
+    match word_list {
+        WordList::Bip39(language) => {
+            let mnemonic_type = MnemonicType::for_word_count(word_count)
+                .map_err(|_| Error::InvalidWordCount(word_count))?;
+            Ok(Mnemonic::new(mnemonic_type, language).into_phrase())
+        }
+        WordList::Waves => {
+            if word_count == 0 {
+                return Err(Error::InvalidWordCount(word_count));
+            }
+            let words = words(Language::English);
+            let mut rng = rand::thread_rng();
+            let phrase: Vec<&str> = (0..word_count)
+                .map(|_| words[rng.gen_range(0..words.len())])
+                .collect();
+            Ok(phrase.join(" "))
+        }
+    }
+}
+
+/// Checks that the phrase consists of words of the word list, and for BIP39 word lists that it
+/// has a valid number of words and a valid checksum. Legacy Waves phrases must have 15 words.
+/// Words may be separated by any whitespace.
+///
+/// # Usage
+/// ```
+/// use wavesplatform::seed::*;
+/// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+/// assert!(validate_phrase(phrase, WordList::Bip39(Language::English)).is_ok());
+/// ```
+pub fn validate_phrase(phrase: &str, word_list: WordList) -> Result<(), Error> {
+    let language = match word_list {
+        WordList::Bip39(language) => language,
+        WordList::Waves => Language::English,
+    };
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    if let Some(word) = words
+        .iter()
+        .find(|word| language.wordmap().get_bits(word).is_err())
+    {
+        return Err(Error::InvalidWord((*word).to_owned()));
+    }
+
+    match word_list {
+        WordList::Bip39(language) => {
+            Mnemonic::validate(&words.join(" "), language).map_err(|err| {
+                match err.downcast_ref::<ErrorKind>() {
+                    Some(ErrorKind::InvalidChecksum) => Error::InvalidPhraseChecksum,
+                    _ => Error::InvalidWordCount(words.len()),
+                }
+            })
+        }
+        WordList::Waves if words.len() != WAVES_WORD_COUNT => {
+            Err(Error::InvalidWordCount(words.len()))
+        }
+        WordList::Waves => Ok(()),
+    }
+}
+
+/// Rough strength of a seed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Strength {
+    /// Less than 64 bits of entropy, can be brute-forced
+    Weak,
+    /// From 64 to 128 bits of entropy
+    Moderate,
+    /// At least 128 bits of entropy
+    Strong,
+}
+
+/// Estimated entropy of a seed, see [`entropy_report`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntropyReport {
+    /// Estimated entropy in bits
+    pub bits: f64,
+    /// Word list of the seed if it is a phrase of one of the known word lists
+    pub word_list: Option<WordList>,
+    pub strength: Strength,
+}
+
+/// Estimates the entropy of an arbitrary seed.
+///
+/// A valid BIP39 phrase has the entropy it was generated from, and a phrase of words of a known
+/// word list has 11 bits per word. For any other seed the estimate is the number of characters
+/// times the bits per character of the character classes it uses, which is an upper bound: human
+/// chosen seeds are much weaker than random strings of the same length.
+///
+/// # Usage
+/// ```
+/// use wavesplatform::seed::*;
+/// let report = entropy_report("password");
+/// assert_eq!(report.strength, Strength::Weak);
+/// ```
+pub fn entropy_report(seed: &str) -> EntropyReport {
+    let words: Vec<&str> = seed.split_whitespace().collect();
+    let bip39 = LANGUAGES.iter().find_map(|&language| {
+        let phrase = words.join(" ");
+        Mnemonic::from_phrase(&phrase, language)
+            .ok()
+            .map(|mnemonic| (language, mnemonic.entropy().len() * 8))
+    });
+    // a single word is more likely a custom seed that happens to be a dictionary word
+    let known_words = LANGUAGES.iter().copied().find(|language| {
+        let wordmap = language.wordmap();
+        words.len() > 1 && words.iter().all(|word| wordmap.get_bits(word).is_ok())
+    });
+
+    let (bits, word_list) = match (bip39, known_words) {
+        (Some((language, bits)), _) => (bits as f64, Some(WordList::Bip39(language))),
+        (None, Some(Language::English)) => (11.0 * words.len() as f64, Some(WordList::Waves)),
+        (None, Some(language)) => (11.0 * words.len() as f64, Some(WordList::Bip39(language))),
+        (None, None) => (character_entropy(seed), None),
+    };
+
+    let strength = match bits {
+        bits if bits < 64.0 => Strength::Weak,
+        bits if bits < 128.0 => Strength::Moderate,
+        _ => Strength::Strong,
+    };
+    EntropyReport {
+        bits,
+        word_list,
+        strength,
+    }
+}
+
+/// Character class of a custom seed and the number of its characters
+type CharClass = (fn(&char) -> bool, u32);
+
+fn character_entropy(seed: &str) -> f64 {
+    let classes: [CharClass; 5] = [
+        (char::is_ascii_lowercase, 26),
+        (char::is_ascii_uppercase, 26),
+        (char::is_ascii_digit, 10),
+        (|c| c.is_ascii() && !c.is_ascii_alphanumeric(), 33),
+        (|c| !c.is_ascii(), 100),
+    ];
+    let pool: u32 = classes
+        .iter()
+        .filter(|(is_class, _)| seed.chars().any(|c| is_class(&c)))
+        .map(|(_, size)| size)
+        .sum();
+    if pool == 0 {
+        return 0.0;
+    }
+    seed.chars().count() as f64 * f64::from(pool).log2()
+}
+
+/// Words of the BIP39 word list of the language
+fn words(language: Language) -> &'static [&'static str] {
+    // every word starts with the empty prefix
+    language.wordlist().get_words_by_prefix("")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const PHRASE: &str =
+        "legal winner thank year wave sausage worth useful legal winner thank yellow";
+
     #[test]
     fn test_generate_phrase() {
         let phrase = generate_phrase();
 
         assert_eq!(phrase.split_ascii_whitespace().count(), 18);
     }
+
+    #[test]
+    fn test_generate_phrase_with() {
+        let phrase = generate_phrase_with(15, WordList::Waves).unwrap();
+        assert_eq!(phrase.split(' ').count(), 15);
+        assert!(validate_phrase(&phrase, WordList::Waves).is_ok());
+
+        for &language in LANGUAGES.iter() {
+            let word_list = WordList::Bip39(language);
+            let phrase = generate_phrase_with(12, word_list).unwrap();
+            assert!(validate_phrase(&phrase, word_list).is_ok());
+        }
+        assert_eq!(words(Language::Japanese).len(), 2048);
+
+        assert!(matches!(
+            generate_phrase_with(13, WordList::Bip39(Language::English)),
+            Err(Error::InvalidWordCount(13))
+        ));
+        assert!(matches!(
+            generate_phrase_with(0, WordList::Waves),
+            Err(Error::InvalidWordCount(0))
+        ));
+    }
+
+    #[test]
+    fn test_validate_phrase() {
+        let english = WordList::Bip39(Language::English);
+        assert!(validate_phrase(PHRASE, english).is_ok());
+        assert!(validate_phrase(&PHRASE.replace(' ', "\n "), english).is_ok());
+        assert!(matches!(
+            validate_phrase(&PHRASE.replace("yellow", "year"), english),
+            Err(Error::InvalidPhraseChecksum)
+        ));
+        assert!(matches!(
+            validate_phrase(&PHRASE.replace("yellow", "yelow"), english),
+            Err(Error::InvalidWord(word)) if word == "yelow"
+        ));
+        assert!(matches!(
+            validate_phrase("legal winner thank", english),
+            Err(Error::InvalidWordCount(3))
+        ));
+        assert!(matches!(
+            validate_phrase(PHRASE, WordList::Waves),
+            Err(Error::InvalidWordCount(12))
+        ));
+    }
+
+    #[test]
+    fn test_entropy_report() {
+        let report = entropy_report(PHRASE);
+        assert_eq!(report.bits, 128.0);
+        assert_eq!(report.word_list, Some(WordList::Bip39(Language::English)));
+        assert_eq!(report.strength, Strength::Strong);
+
+        let phrase = "body key praise enter toss road cup result shrimp bus blame typical sphere pottery claim";
+        let report = entropy_report(phrase);
+        assert_eq!(report.bits, 165.0);
+        assert_eq!(report.word_list, Some(WordList::Waves));
+
+        let report = entropy_report("correct horse");
+        assert_eq!(report.bits, 22.0);
+        assert_eq!(report.strength, Strength::Weak);
+
+        let report = entropy_report("Tr0ub4dor&3");
+        assert_eq!(report.word_list, None);
+        assert!((report.bits - 11.0 * 95f64.log2()).abs() < 1e-9);
+        assert_eq!(report.strength, Strength::Moderate);
+
+        assert_eq!(entropy_report("").bits, 0.0);
+    }
 }
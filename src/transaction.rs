@@ -1,18 +1,39 @@
+mod builder;
 mod data_entry;
+mod function_call;
 mod hash;
+mod json;
+mod order;
+mod parse_error;
+mod protobuf;
+mod recipient;
 mod transaction_data;
 mod type_id;
 mod version;
 
-use crate::account::{blake_hash, Address, PublicKeyAccount};
-use crate::bytebuffer::Buffer;
+use crate::account::{blake_hash, PublicKeyAccount, MAINNET};
+use crate::bytebuffer::{Buffer, Reader};
+use crate::error::Error;
+use crate::util::sig_verify;
 
+use ed25519_dalek::SIGNATURE_LENGTH;
+
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+pub use builder::*;
 pub use data_entry::*;
+pub use function_call::*;
 pub use hash::*;
+pub use order::*;
+pub use parse_error::*;
+pub use recipient::*;
 pub use transaction_data::*;
 pub use type_id::*;
 pub use version::*;
 
+pub(crate) use protobuf::data_payload_size;
+
 /// Transaction data. Data specific to a particular transaction type are stored in the `data` field.
 /// # Usage
 /// ```
@@ -28,12 +49,13 @@ pub use version::*;
 /// );
 /// let signed_tx = account.sign_transaction(tx);
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Transaction<'a> {
     data: TransactionData<'a>,
     fee: u64,
     timestamp: u64,
-    sender_public_key: &'a PublicKeyAccount,
+    sender_public_key: PublicKeyAccount,
+    chain_id: u8,
     type_id: u8,
     version: u8,
 }
@@ -56,17 +78,17 @@ impl<'a> Transaction<'a> {
     ) -> Transaction<'a> {
         Transaction {
             data: Issue {
-                name,
-                description,
+                name: name.into(),
+                description: description.into(),
                 quantity,
                 decimals,
                 reissuable,
-                chain_id,
-                script,
+                script: script.map(Cow::from),
             },
             fee,
             timestamp,
-            sender_public_key,
+            sender_public_key: *sender_public_key,
+            chain_id,
             type_id: Type::Issue as u8,
             version: Version::V2 as u8,
         }
@@ -75,7 +97,7 @@ impl<'a> Transaction<'a> {
     #[allow(clippy::too_many_arguments)]
     pub fn new_transfer(
         sender_public_key: &'a PublicKeyAccount,
-        recipient: &'a Address,
+        recipient: &'a Recipient,
         asset: Option<&'a Asset>,
         amount: u64,
         fee_asset: Option<&'a Asset>,
@@ -85,15 +107,16 @@ impl<'a> Transaction<'a> {
     ) -> Transaction<'a> {
         Transaction {
             data: Transfer {
-                recipient,
-                asset,
+                recipient: recipient.clone(),
+                asset: asset.copied(),
                 amount,
-                fee_asset,
-                attachment,
+                fee_asset: fee_asset.copied(),
+                attachment: attachment.map(|s| s.as_bytes().into()),
             },
             fee,
             timestamp,
-            sender_public_key,
+            sender_public_key: *sender_public_key,
+            chain_id: recipient.chain_id(),
             type_id: Type::Transfer as u8,
             version: Version::V2 as u8,
         }
@@ -110,14 +133,14 @@ impl<'a> Transaction<'a> {
     ) -> Transaction<'a> {
         Transaction {
             data: Reissue {
-                asset,
+                asset: *asset,
                 quantity,
                 reissuable,
-                chain_id,
             },
             fee,
             timestamp,
-            sender_public_key,
+            sender_public_key: *sender_public_key,
+            chain_id,
             type_id: Type::Reissue as u8,
             version: Version::V2 as u8,
         }
@@ -133,21 +156,62 @@ impl<'a> Transaction<'a> {
     ) -> Transaction<'a> {
         Transaction {
             data: Burn {
-                asset,
+                asset: *asset,
                 quantity,
-                chain_id,
             },
             fee,
             timestamp,
-            sender_public_key,
+            sender_public_key: *sender_public_key,
+            chain_id,
             type_id: Type::Burn as u8,
             version: Version::V2 as u8,
         }
     }
 
+    /// Creates an Exchange transaction of two orders matched by the sender, their matcher. The
+    /// transaction is of version 2, or of version 3 if either order is of version 4, and has the
+    /// chain ID of the first order. Legacy bytes have no chain ID, so parsed version 2
+    /// transactions and their orders are on [`MAINNET`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_exchange(
+        sender_public_key: &'a PublicKeyAccount,
+        order1: &'a ProvenOrder,
+        order2: &'a ProvenOrder,
+        amount: u64,
+        price: u64,
+        buy_matcher_fee: u64,
+        sell_matcher_fee: u64,
+        fee: u64,
+        timestamp: u64,
+    ) -> Transaction<'a> {
+        let version = if order1.order.version >= Version::V4 as u8
+            || order2.order.version >= Version::V4 as u8
+        {
+            Version::V3
+        } else {
+            Version::V2
+        };
+        Transaction {
+            data: Exchange {
+                order1: order1.clone(),
+                order2: order2.clone(),
+                amount,
+                price,
+                buy_matcher_fee,
+                sell_matcher_fee,
+            },
+            fee,
+            timestamp,
+            sender_public_key: *sender_public_key,
+            chain_id: order1.order.chain_id,
+            type_id: Type::Exchange as u8,
+            version: version as u8,
+        }
+    }
+
     pub fn new_lease(
         sender_public_key: &'a PublicKeyAccount,
-        recipient: &'a Address,
+        recipient: &'a Recipient,
         amount: u64,
         chain_id: u8,
         fee: u64,
@@ -155,13 +219,13 @@ impl<'a> Transaction<'a> {
     ) -> Transaction<'a> {
         Transaction {
             data: Lease {
-                recipient,
+                recipient: recipient.clone(),
                 amount,
-                chain_id,
             },
             fee,
             timestamp,
-            sender_public_key,
+            sender_public_key: *sender_public_key,
+            chain_id,
             type_id: Type::Lease as u8,
             version: Version::V2 as u8,
         }
@@ -175,10 +239,13 @@ impl<'a> Transaction<'a> {
         timestamp: u64,
     ) -> Transaction<'a> {
         Transaction {
-            data: CancelLease { lease_id, chain_id },
+            data: CancelLease {
+                lease_id: *lease_id,
+            },
             fee,
             timestamp,
-            sender_public_key,
+            sender_public_key: *sender_public_key,
+            chain_id,
             type_id: Type::LeaseCancel as u8,
             version: Version::V2 as u8,
         }
@@ -192,37 +259,51 @@ impl<'a> Transaction<'a> {
         timestamp: u64,
     ) -> Transaction<'a> {
         Transaction {
-            data: Alias { alias, chain_id },
+            data: Alias {
+                alias: alias.into(),
+            },
             fee,
             timestamp,
-            sender_public_key,
+            sender_public_key: *sender_public_key,
+            chain_id,
             type_id: Type::Alias as u8,
             version: Version::V2 as u8,
         }
     }
 
+    /// Creates a Mass Transfer transaction. The chain ID is taken from the first recipient, or is
+    /// [`MAINNET`] if there are no transfers; use [`Transaction::with_chain_id`] to change it.
     pub fn new_mass_transfer(
         sender_public_key: &'a PublicKeyAccount,
         asset: Option<&'a Asset>,
-        transfers: Vec<(&'a Address, u64)>,
+        transfers: Vec<(&'a Recipient, u64)>,
         attachment: Option<&'a str>,
         fee: u64,
         timestamp: u64,
     ) -> Transaction<'a> {
+        let chain_id = transfers
+            .first()
+            .map(|(recipient, _)| recipient.chain_id())
+            .unwrap_or(MAINNET);
         Transaction {
             data: MassTransfer {
-                asset,
-                transfers,
-                attachment,
+                asset: asset.copied(),
+                transfers: transfers
+                    .into_iter()
+                    .map(|(recipient, amt)| (recipient.clone(), amt))
+                    .collect(),
+                attachment: attachment.map(|s| s.as_bytes().into()),
             },
             fee,
             timestamp,
-            sender_public_key,
+            sender_public_key: *sender_public_key,
+            chain_id,
             type_id: Type::MassTransfer as u8,
             version: Version::V1 as u8,
         }
     }
 
+    /// Creates a Data transaction on [`MAINNET`]; use [`Transaction::with_chain_id`] to change it.
     pub fn new_data(
         sender_public_key: &'a PublicKeyAccount,
         data: Vec<&'a DataEntry<'a>>,
@@ -230,10 +311,13 @@ impl<'a> Transaction<'a> {
         timestamp: u64,
     ) -> Transaction<'a> {
         Transaction {
-            data: Data { data },
+            data: Data {
+                data: data.into_iter().cloned().collect(),
+            },
             fee,
             timestamp,
-            sender_public_key,
+            sender_public_key: *sender_public_key,
+            chain_id: MAINNET,
             type_id: Type::Data as u8,
             version: Version::V1 as u8,
         }
@@ -247,15 +331,20 @@ impl<'a> Transaction<'a> {
         timestamp: u64,
     ) -> Transaction<'a> {
         Transaction {
-            data: SetScript { script, chain_id },
+            data: SetScript {
+                script: script.map(Cow::from),
+            },
             fee,
             timestamp,
-            sender_public_key,
+            sender_public_key: *sender_public_key,
+            chain_id,
             type_id: Type::SetScript as u8,
             version: Version::V1 as u8,
         }
     }
 
+    /// Creates a Sponsor Fee transaction on [`MAINNET`]; use [`Transaction::with_chain_id`] to
+    /// change it.
     pub fn new_sponsor(
         sender_public_key: &'a PublicKeyAccount,
         asset: &'a Asset,
@@ -264,10 +353,14 @@ impl<'a> Transaction<'a> {
         timestamp: u64,
     ) -> Transaction<'a> {
         Transaction {
-            data: Sponsor { asset, rate },
+            data: Sponsor {
+                asset: *asset,
+                rate,
+            },
             fee,
             timestamp,
-            sender_public_key,
+            sender_public_key: *sender_public_key,
+            chain_id: MAINNET,
             type_id: Type::Sponsor as u8,
             version: Version::V1 as u8,
         }
@@ -283,125 +376,299 @@ impl<'a> Transaction<'a> {
     ) -> Transaction<'a> {
         Transaction {
             data: SetAssetScript {
-                asset,
-                script,
-                chain_id,
+                asset: *asset,
+                script: script.map(Cow::from),
             },
             fee,
             timestamp,
-            sender_public_key,
+            sender_public_key: *sender_public_key,
+            chain_id,
             type_id: Type::SetAssetScript as u8,
             version: Version::V1 as u8,
         }
     }
 
+    /// Creates an Invoke Script transaction calling `call` of the `dapp`, or its default function
+    /// if `call` is `None`, with the given payments in WAVES (`None`) or assets
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_invoke_script(
+        sender_public_key: &'a PublicKeyAccount,
+        dapp: &'a Recipient,
+        call: Option<&'a FunctionCall<'a>>,
+        payments: Vec<(Option<&'a Asset>, u64)>,
+        fee_asset: Option<&'a Asset>,
+        fee: u64,
+        timestamp: u64,
+    ) -> Transaction<'a> {
+        Transaction {
+            data: InvokeScript {
+                dapp: dapp.clone(),
+                call: call.cloned(),
+                payments: payments
+                    .into_iter()
+                    .map(|(asset, amt)| (asset.copied(), amt))
+                    .collect(),
+                fee_asset: fee_asset.copied(),
+            },
+            fee,
+            timestamp,
+            sender_public_key: *sender_public_key,
+            chain_id: dapp.chain_id(),
+            type_id: Type::InvokeScript as u8,
+            version: Version::V1 as u8,
+        }
+    }
+
+    /// Creates an Update Asset Info transaction renaming the asset and replacing its description.
+    /// The transaction only exists in the protobuf format, of version 1.
+    pub fn new_update_asset_info(
+        sender_public_key: &'a PublicKeyAccount,
+        asset: &'a Asset,
+        name: &'a str,
+        description: &'a str,
+        chain_id: u8,
+        fee: u64,
+        timestamp: u64,
+    ) -> Transaction<'a> {
+        Transaction {
+            data: UpdateAssetInfo {
+                asset: *asset,
+                name: name.into(),
+                description: description.into(),
+            },
+            fee,
+            timestamp,
+            sender_public_key: *sender_public_key,
+            chain_id,
+            type_id: Type::UpdateAssetInfo as u8,
+            version: Version::V1 as u8,
+        }
+    }
+
+    /// Sets the transaction version.
+    ///
+    /// Constructors create transactions of the legacy binary version of their type. Any later
+    /// version, e.g. [`Version::V3`], makes the transaction signed and identified by its protobuf
+    /// encoding, which is the only one of Update Asset Info transactions. Note that the node
+    /// expects version 2 for protobuf Mass Transfer, Data, Set Script, Sponsor Fee and Set Asset
+    /// Script transactions. [`Version::V1`] of the types whose legacy version is 2 is the
+    /// original format signed with a single signature, which has no chain ID and no Issue script.
+    pub fn with_version(mut self, version: Version) -> Transaction<'a> {
+        self.version = version as u8;
+        self
+    }
+
+    /// Sets the chain ID, which is part of every protobuf transaction.
+    pub fn with_chain_id(mut self, chain_id: u8) -> Transaction<'a> {
+        self.chain_id = chain_id;
+        self
+    }
+
+    /// Sets the fee in WAVELET, or in the fee asset if the transaction has one.
+    pub fn with_fee(mut self, fee: u64) -> Transaction<'a> {
+        self.fee = fee;
+        self
+    }
+
+    /// Returns the data specific to the transaction type
+    pub fn data(&self) -> &TransactionData<'a> {
+        &self.data
+    }
+
+    /// Returns the fee
+    pub fn fee(&self) -> u64 {
+        self.fee
+    }
+
+    /// Returns the timestamp in milliseconds
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Returns the public key of the sender
+    pub fn sender_public_key(&self) -> &PublicKeyAccount {
+        &self.sender_public_key
+    }
+
+    /// Returns the chain ID
+    pub fn chain_id(&self) -> u8 {
+        self.chain_id
+    }
+
+    /// Returns the transaction type ID
+    pub fn type_id(&self) -> u8 {
+        self.type_id
+    }
+
+    /// Returns the transaction version
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns `true` if the transaction is encoded with protobuf, i.e. its version is newer than
+    /// the legacy version of its type, or it has no legacy encoding at all
+    pub(crate) fn is_protobuf(&self) -> bool {
+        matches!(self.data, UpdateAssetInfo { .. })
+            || Type::try_from(self.type_id).is_ok_and(|t| is_protobuf_version(t, self.version))
+    }
+
+    /// Returns `true` if the transaction is of the version 1 format that predates proofs, i.e. a
+    /// version 1 Issue, Transfer, Reissue, Burn, Exchange, Lease, Lease Cancel or Alias
+    pub(crate) fn is_signature_version(&self) -> bool {
+        self.version == Version::V1 as u8
+            && Type::try_from(self.type_id).is_ok_and(has_signature_version)
+    }
+
+    /// Returns the bytes to sign: the legacy binary body or, for protobuf versions, the
+    /// `Transaction` message.
     pub fn to_bytes(&self) -> Vec<u8> {
+        if self.is_protobuf() {
+            return self.to_protobuf();
+        }
+
+        let signature_version = self.is_signature_version();
         let mut buf = Buffer::new();
-        buf.byte(self.type_id).byte(self.version);
-        match self.data {
+        if let (Exchange { .. }, false) = (&self.data, signature_version) {
+            // unlike other types, the Exchange body starts with a zero byte
+            buf.byte(0);
+        }
+        buf.byte(self.type_id);
+        if !signature_version {
+            buf.byte(self.version);
+        }
+        // version 1 bodies have neither the version nor the chain ID
+        let chain_id = |buf: &mut Buffer| {
+            if !signature_version {
+                buf.byte(self.chain_id);
+            }
+        };
+        match &self.data {
             Issue {
                 name,
                 description,
                 quantity,
                 decimals,
                 reissuable,
-                chain_id,
                 script,
             } => {
-                buf.byte(chain_id)
-                    .bytes(self.sender_public_key.to_bytes())
+                chain_id(&mut buf);
+                buf.bytes(self.sender_public_key.to_bytes())
                     .array(name.as_bytes())
                     .array(description.as_bytes())
-                    .long(quantity)
-                    .byte(decimals)
-                    .boolean(reissuable)
+                    .long(*quantity)
+                    .byte(*decimals)
+                    .boolean(*reissuable)
                     .long(self.fee)
                     .long(self.timestamp);
-                match script {
-                    Some(bytes) => buf.byte(1).array(bytes),
-                    None => buf.byte(0),
+                if !signature_version {
+                    buf.script_opt(script.as_deref());
                 }
+                &mut buf
             }
             Transfer {
                 recipient,
-                ref asset,
+                asset,
                 amount,
-                ref fee_asset,
+                fee_asset,
                 attachment,
             } => buf
                 .bytes(self.sender_public_key.to_bytes())
-                .asset_opt(asset)
-                .asset_opt(fee_asset)
+                .asset_opt(asset.as_ref())
+                .asset_opt(fee_asset.as_ref())
                 .long(self.timestamp)
-                .long(amount)
+                .long(*amount)
                 .long(self.fee)
-                .recipient(recipient.chain_id(), &recipient.to_string())
-                .array_opt(attachment.map(|s| s.as_bytes())),
+                .recipient(recipient)
+                .array_opt(attachment.as_deref()),
             Reissue {
                 asset,
                 quantity,
                 reissuable,
-                chain_id,
-            } => buf
-                .byte(chain_id)
-                .bytes(self.sender_public_key.to_bytes())
-                .asset(asset)
-                .long(quantity)
-                .boolean(reissuable)
-                .long(self.fee)
-                .long(self.timestamp),
-            Burn {
-                asset,
-                quantity,
-                chain_id,
-            } => buf
-                .byte(chain_id)
-                .bytes(self.sender_public_key.to_bytes())
-                .asset(asset)
-                .long(quantity)
-                .long(self.fee)
-                .long(self.timestamp),
-            Lease {
-                recipient,
+            } => {
+                chain_id(&mut buf);
+                buf.bytes(self.sender_public_key.to_bytes())
+                    .asset(asset)
+                    .long(*quantity)
+                    .boolean(*reissuable)
+                    .long(self.fee)
+                    .long(self.timestamp)
+            }
+            Burn { asset, quantity } => {
+                chain_id(&mut buf);
+                buf.bytes(self.sender_public_key.to_bytes())
+                    .asset(asset)
+                    .long(*quantity)
+                    .long(self.fee)
+                    .long(self.timestamp)
+            }
+            Exchange {
+                order1,
+                order2,
                 amount,
-                chain_id,
-            } => buf
-                .byte(0)
-                .bytes(self.sender_public_key.to_bytes())
-                .recipient(chain_id, &recipient.to_string())
-                .long(amount)
-                .long(self.fee)
-                .long(self.timestamp),
-            CancelLease { lease_id, chain_id } => buf
-                .byte(chain_id)
-                .bytes(self.sender_public_key.to_bytes())
-                .long(self.fee)
-                .long(self.timestamp)
-                .bytes(&lease_id.to_bytes()),
-            Alias { alias, chain_id } => buf
+                price,
+                buy_matcher_fee,
+                sell_matcher_fee,
+            } => {
+                if signature_version {
+                    // version 1 has both order lengths first and no version marks
+                    let (order1, order2) = (order1.to_bytes(), order2.to_bytes());
+                    buf.int(order1.len() as u32)
+                        .int(order2.len() as u32)
+                        .bytes(&order1)
+                        .bytes(&order2);
+                } else {
+                    order1.write(&mut buf);
+                    order2.write(&mut buf);
+                }
+                buf.long(*price)
+                    .long(*amount)
+                    .long(*buy_matcher_fee)
+                    .long(*sell_matcher_fee)
+                    .long(self.fee)
+                    .long(self.timestamp)
+            }
+            Lease { recipient, amount } => {
+                if !signature_version {
+                    // the asset of the lease, always WAVES
+                    buf.byte(0);
+                }
+                buf.bytes(self.sender_public_key.to_bytes())
+                    .recipient(recipient)
+                    .long(*amount)
+                    .long(self.fee)
+                    .long(self.timestamp)
+            }
+            CancelLease { lease_id } => {
+                chain_id(&mut buf);
+                buf.bytes(self.sender_public_key.to_bytes())
+                    .long(self.fee)
+                    .long(self.timestamp)
+                    .bytes(&lease_id.to_bytes())
+            }
+            Alias { alias } => buf
                 .bytes(self.sender_public_key.to_bytes())
                 .size(alias.len() + 4)
                 .byte(2)
-                .byte(chain_id)
+                .byte(self.chain_id)
                 .array(alias.as_bytes())
                 .long(self.fee)
                 .long(self.timestamp),
             MassTransfer {
-                ref asset,
-                ref transfers,
+                asset,
+                transfers,
                 attachment,
             } => {
                 buf.bytes(self.sender_public_key.to_bytes())
-                    .asset_opt(asset)
+                    .asset_opt(asset.as_ref())
                     .size(transfers.len());
-                for (addr, amt) in transfers {
-                    buf.bytes(addr.to_bytes()).long(*amt);
+                for (recipient, amt) in transfers {
+                    buf.recipient(recipient).long(*amt);
                 }
                 buf.long(self.timestamp)
                     .long(self.fee)
-                    .array_opt(attachment.map(|s| s.as_bytes()))
+                    .array_opt(attachment.as_deref())
             }
-            Data { ref data } => {
+            Data { data } => {
                 buf.bytes(self.sender_public_key.to_bytes())
                     .size(data.len());
                 for e in data {
@@ -409,47 +676,360 @@ impl<'a> Transaction<'a> {
                 }
                 buf.long(self.timestamp).long(self.fee)
             }
-            SetScript { script, chain_id } => {
-                buf.byte(chain_id).bytes(self.sender_public_key.to_bytes());
-                match script {
-                    Some(bytes) => buf.byte(1).array(bytes),
-                    None => buf.byte(0),
-                };
-                buf.long(self.fee).long(self.timestamp)
-            }
+            SetScript { script } => buf
+                .byte(self.chain_id)
+                .bytes(self.sender_public_key.to_bytes())
+                .script_opt(script.as_deref())
+                .long(self.fee)
+                .long(self.timestamp),
             Sponsor { asset, rate } => buf
                 .bytes(self.sender_public_key.to_bytes())
                 .asset(asset)
                 .long(rate.unwrap_or(0))
                 .long(self.fee)
                 .long(self.timestamp),
-            SetAssetScript {
-                asset,
-                script,
-                chain_id,
+            SetAssetScript { asset, script } => buf
+                .byte(self.chain_id)
+                .bytes(self.sender_public_key.to_bytes())
+                .asset(asset)
+                .script_opt(script.as_deref())
+                .long(self.fee)
+                .long(self.timestamp),
+            InvokeScript {
+                dapp,
+                call,
+                payments,
+                fee_asset,
             } => {
-                buf.byte(chain_id)
+                buf.byte(self.chain_id)
                     .bytes(self.sender_public_key.to_bytes())
-                    .asset(asset);
-                match script {
-                    Some(bytes) => buf.byte(1).array(bytes),
+                    .recipient(dapp);
+                match call {
+                    Some(call) => buf.byte(1).function_call(call),
                     None => buf.byte(0),
                 };
-                buf.long(self.fee).long(self.timestamp)
+                buf.size(payments.len());
+                for (asset, amt) in payments {
+                    let mut payment = Buffer::new();
+                    payment.long(*amt).asset_opt(asset.as_ref());
+                    buf.array(payment.as_slice());
+                }
+                buf.long(self.fee)
+                    .asset_opt(fee_asset.as_ref())
+                    .long(self.timestamp)
             }
+            UpdateAssetInfo { .. } => return self.to_protobuf(),
         };
         Vec::from(buf.as_slice())
     }
 
+    /// Parses a transaction from the bytes returned by [`Transaction::to_bytes`]: either a legacy
+    /// binary body or a protobuf `Transaction` message.
+    ///
+    /// Strings and scripts of a legacy transaction borrow from `bytes`.
+    ///
+    /// A version 1 body of the types signed with a single signature has no version byte, so it is
+    /// only read as such if the bytes are no other body.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Transaction<'a>, ParseError> {
+        let result = if is_protobuf_body(bytes) {
+            Transaction::from_protobuf(bytes)
+        } else {
+            let mut reader = Reader::new(bytes);
+            if let [0, type_id, ..] = bytes {
+                if *type_id == Type::Exchange as u8 {
+                    reader.byte()?;
+                }
+            }
+            Self::read(&mut reader, false).and_then(|tx| reader.finish().map(|_| tx))
+        };
+        if result.is_err() && starts_with_signature_type(bytes) {
+            let mut reader = Reader::new(bytes);
+            if let Ok(tx) = Self::read(&mut reader, true).and_then(|tx| reader.finish().map(|_| tx))
+            {
+                return Ok(tx);
+            }
+        }
+        result
+    }
+
+    /// Reads a legacy body, or a version 1 body without the version byte if `signature_version`
+    /// is set
+    fn read(
+        reader: &mut Reader<'a>,
+        signature_version: bool,
+    ) -> Result<Transaction<'a>, ParseError> {
+        let type_id = reader.byte()?;
+        let tx_type = Type::try_from(type_id)?;
+        let version = if signature_version {
+            if !has_signature_version(tx_type) {
+                return Err(ParseError::UnsupportedVersion(type_id, Version::V1 as u8));
+            }
+            Version::V1 as u8
+        } else {
+            reader.byte()?
+        };
+        if !signature_version && legacy_version(tx_type).map(|v| v as u8) != Some(version) {
+            return Err(ParseError::UnsupportedVersion(type_id, version));
+        }
+        // version 1 bodies have no chain ID, so it is only known from the recipient addresses
+        let read_chain_id = |reader: &mut Reader| {
+            if signature_version {
+                Ok(MAINNET)
+            } else {
+                reader.byte()
+            }
+        };
+
+        let (sender_public_key, chain_id, data, fee, timestamp) = match tx_type {
+            Type::Issue => {
+                let chain_id = read_chain_id(reader)?;
+                let sender = reader.public_key()?;
+                let name = reader.string()?.into();
+                let description = reader.string()?.into();
+                let quantity = reader.long()?;
+                let decimals = reader.byte()?;
+                let reissuable = reader.boolean()?;
+                let fee = reader.long()?;
+                let timestamp = reader.long()?;
+                let script = if signature_version {
+                    None
+                } else {
+                    reader.script_opt()?.map(Cow::from)
+                };
+                let data = Issue {
+                    name,
+                    description,
+                    quantity,
+                    decimals,
+                    reissuable,
+                    script,
+                };
+                (sender, chain_id, data, fee, timestamp)
+            }
+            Type::Transfer => {
+                let sender = reader.public_key()?;
+                let asset = reader.asset_opt()?;
+                let fee_asset = reader.asset_opt()?;
+                let timestamp = reader.long()?;
+                let amount = reader.long()?;
+                let fee = reader.long()?;
+                let recipient = reader.recipient()?;
+                let chain_id = recipient.chain_id();
+                let attachment = reader.array_opt()?.map(Cow::from);
+                let data = Transfer {
+                    recipient,
+                    asset,
+                    amount,
+                    fee_asset,
+                    attachment,
+                };
+                (sender, chain_id, data, fee, timestamp)
+            }
+            Type::Reissue => {
+                let chain_id = read_chain_id(reader)?;
+                let sender = reader.public_key()?;
+                let asset = reader.asset()?;
+                let quantity = reader.long()?;
+                let reissuable = reader.boolean()?;
+                let fee = reader.long()?;
+                let timestamp = reader.long()?;
+                let data = Reissue {
+                    asset,
+                    quantity,
+                    reissuable,
+                };
+                (sender, chain_id, data, fee, timestamp)
+            }
+            Type::Burn => {
+                let chain_id = read_chain_id(reader)?;
+                let sender = reader.public_key()?;
+                let asset = reader.asset()?;
+                let quantity = reader.long()?;
+                let fee = reader.long()?;
+                let timestamp = reader.long()?;
+                (sender, chain_id, Burn { asset, quantity }, fee, timestamp)
+            }
+            Type::Exchange => {
+                let (order1, order2) = if signature_version {
+                    let len1 = reader.int()? as usize;
+                    let len2 = reader.int()? as usize;
+                    let v1 = Version::V1 as u8;
+                    (
+                        ProvenOrder::read_signed(reader.bytes(len1)?, v1, MAINNET)?,
+                        ProvenOrder::read_signed(reader.bytes(len2)?, v1, MAINNET)?,
+                    )
+                } else {
+                    (
+                        ProvenOrder::read(reader, MAINNET)?,
+                        ProvenOrder::read(reader, MAINNET)?,
+                    )
+                };
+                let price = reader.long()?;
+                let amount = reader.long()?;
+                let buy_matcher_fee = reader.long()?;
+                let sell_matcher_fee = reader.long()?;
+                let fee = reader.long()?;
+                let timestamp = reader.long()?;
+                // the sender of a legacy Exchange is the matcher of its orders
+                let sender = order1.order.matcher_public_key;
+                let data = Exchange {
+                    order1,
+                    order2,
+                    amount,
+                    price,
+                    buy_matcher_fee,
+                    sell_matcher_fee,
+                };
+                (sender, MAINNET, data, fee, timestamp)
+            }
+            Type::Lease => {
+                if !signature_version && reader.boolean()? {
+                    return Err(ParseError::InvalidFlag(1));
+                }
+                let sender = reader.public_key()?;
+                let recipient = reader.recipient()?;
+                let amount = reader.long()?;
+                let fee = reader.long()?;
+                let timestamp = reader.long()?;
+                let chain_id = recipient.chain_id();
+                let data = Lease { recipient, amount };
+                (sender, chain_id, data, fee, timestamp)
+            }
+            Type::LeaseCancel => {
+                let chain_id = read_chain_id(reader)?;
+                let sender = reader.public_key()?;
+                let fee = reader.long()?;
+                let timestamp = reader.long()?;
+                let lease_id = reader.hash()?;
+                (sender, chain_id, CancelLease { lease_id }, fee, timestamp)
+            }
+            Type::Alias => {
+                let sender = reader.public_key()?;
+                let mut alias_reader = Reader::new(reader.array()?);
+                match alias_reader.byte()? {
+                    2 => {}
+                    marker => return Err(ParseError::UnsupportedRecipient(marker)),
+                }
+                let chain_id = alias_reader.byte()?;
+                let alias = alias_reader.string()?.into();
+                alias_reader.finish()?;
+                let fee = reader.long()?;
+                let timestamp = reader.long()?;
+                (sender, chain_id, Alias { alias }, fee, timestamp)
+            }
+            Type::MassTransfer => {
+                let sender = reader.public_key()?;
+                let asset = reader.asset_opt()?;
+                let count = reader.size()?;
+                let mut transfers = Vec::with_capacity(count);
+                for _ in 0..count {
+                    transfers.push((reader.recipient()?, reader.long()?));
+                }
+                let timestamp = reader.long()?;
+                let fee = reader.long()?;
+                let attachment = reader.array_opt()?.map(Cow::from);
+                let chain_id = transfers
+                    .first()
+                    .map(|(recipient, _)| recipient.chain_id())
+                    .unwrap_or(MAINNET);
+                let data = MassTransfer {
+                    asset,
+                    transfers,
+                    attachment,
+                };
+                (sender, chain_id, data, fee, timestamp)
+            }
+            Type::Data => {
+                let sender = reader.public_key()?;
+                let count = reader.size()?;
+                let mut data = Vec::with_capacity(count);
+                for _ in 0..count {
+                    data.push(reader.data_entry()?);
+                }
+                let timestamp = reader.long()?;
+                let fee = reader.long()?;
+                (sender, MAINNET, Data { data }, fee, timestamp)
+            }
+            Type::SetScript => {
+                let chain_id = reader.byte()?;
+                let sender = reader.public_key()?;
+                let script = reader.script_opt()?.map(Cow::from);
+                let fee = reader.long()?;
+                let timestamp = reader.long()?;
+                (sender, chain_id, SetScript { script }, fee, timestamp)
+            }
+            Type::Sponsor => {
+                let sender = reader.public_key()?;
+                let asset = reader.asset()?;
+                let rate = match reader.long()? {
+                    0 => None,
+                    rate => Some(rate),
+                };
+                let fee = reader.long()?;
+                let timestamp = reader.long()?;
+                (sender, MAINNET, Sponsor { asset, rate }, fee, timestamp)
+            }
+            Type::SetAssetScript => {
+                let chain_id = reader.byte()?;
+                let sender = reader.public_key()?;
+                let asset = reader.asset()?;
+                let script = reader.script_opt()?.map(Cow::from);
+                let fee = reader.long()?;
+                let timestamp = reader.long()?;
+                let data = SetAssetScript { asset, script };
+                (sender, chain_id, data, fee, timestamp)
+            }
+            Type::InvokeScript => {
+                let chain_id = reader.byte()?;
+                let sender = reader.public_key()?;
+                let dapp = reader.recipient()?;
+                let call = if reader.boolean()? {
+                    Some(reader.function_call()?)
+                } else {
+                    None
+                };
+                let count = reader.size()?;
+                let mut payments = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let mut payment = Reader::new(reader.array()?);
+                    let amt = payment.long()?;
+                    payments.push((payment.asset_opt()?, amt));
+                    payment.finish()?;
+                }
+                let fee = reader.long()?;
+                let fee_asset = reader.asset_opt()?;
+                let timestamp = reader.long()?;
+                let data = InvokeScript {
+                    dapp,
+                    call,
+                    payments,
+                    fee_asset,
+                };
+                (sender, chain_id, data, fee, timestamp)
+            }
+            Type::UpdateAssetInfo => return Err(ParseError::UnsupportedVersion(type_id, version)),
+        };
+
+        Ok(Transaction {
+            data,
+            fee,
+            timestamp,
+            sender_public_key,
+            chain_id,
+            type_id,
+            version,
+        })
+    }
+
     /// Returns transaction ID
     pub fn id(&self) -> TransactionId {
-        let bytes = match self.data {
-            Alias { alias, chain_id } => {
+        let bytes = match &self.data {
+            Alias { alias } if !self.is_protobuf() => {
                 let mut buf = Buffer::new();
                 Vec::from(
                     buf.byte(self.type_id)
                         .byte(2)
-                        .byte(chain_id)
+                        .byte(self.chain_id)
                         .array(alias.as_bytes())
                         .as_slice(),
                 )
@@ -465,15 +1045,255 @@ impl<'a> Transaction<'a> {
     pub fn with_proofs(self, proofs: Vec<Vec<u8>>) -> ProvenTransaction<'a> {
         ProvenTransaction { tx: self, proofs }
     }
+
+    /// Converts the transaction to one that owns all its data, so that it can outlive the values
+    /// it was created from, e.g. to be returned from a function or sent to another task.
+    /// ```
+    /// use wavesplatform::account::{PublicKeyAccount, TESTNET};
+    /// use wavesplatform::transaction::Transaction;
+    ///
+    /// fn alias_tx(sender: &PublicKeyAccount, alias: String) -> Transaction<'static> {
+    ///     Transaction::new_alias(sender, &alias, TESTNET, 100000, 1536000000000).into_owned()
+    /// }
+    /// ```
+    pub fn into_owned(self) -> Transaction<'static> {
+        Transaction {
+            data: self.data.into_owned(),
+            fee: self.fee,
+            timestamp: self.timestamp,
+            sender_public_key: self.sender_public_key,
+            chain_id: self.chain_id,
+            type_id: self.type_id,
+            version: self.version,
+        }
+    }
+}
+
+/// Returns the legacy (pre-protobuf) transaction version this library supports for the given
+/// type, or `None` if the type only exists as a protobuf transaction
+fn legacy_version(tx_type: Type) -> Option<Version> {
+    match tx_type {
+        Type::UpdateAssetInfo => None,
+        Type::MassTransfer
+        | Type::Data
+        | Type::SetScript
+        | Type::Sponsor
+        | Type::SetAssetScript
+        | Type::InvokeScript => Some(Version::V1),
+        _ => Some(Version::V2),
+    }
+}
+
+/// Returns `true` if version 1 of the given type is the format signed with a single signature,
+/// which the node still returns for old transactions
+fn has_signature_version(tx_type: Type) -> bool {
+    legacy_version(tx_type) == Some(Version::V2)
+}
+
+/// Returns `true` if the bytes start with the ID of a type that has a version 1 signature format
+fn starts_with_signature_type(bytes: &[u8]) -> bool {
+    bytes
+        .first()
+        .is_some_and(|type_id| Type::try_from(*type_id).is_ok_and(has_signature_version))
+}
+
+/// Returns the latest transaction version this library supports for the given type
+fn latest_version(tx_type: Type) -> Version {
+    match legacy_version(tx_type) {
+        None => Version::V1,
+        Some(Version::V1) => Version::V2,
+        Some(_) => Version::V3,
+    }
+}
+
+/// Returns `true` if transactions of the given type and version are encoded with protobuf
+fn is_protobuf_version(tx_type: Type, version: u8) -> bool {
+    legacy_version(tx_type).is_none_or(|legacy| version > legacy as u8)
+}
+
+/// Tells a protobuf `Transaction` message from a legacy body, which starts with the type ID and
+/// the version. The protobuf message starts with the chain ID field (tag `0x08`), which is
+/// also the Lease type ID, but a legacy Lease is always followed by version 2, and no network
+/// has chain ID 2.
+fn is_protobuf_body(bytes: &[u8]) -> bool {
+    matches!(bytes, [8, second, ..] if *second != Version::V2 as u8)
 }
 
+/// Returns `true` if the signature precedes the body in version 1 signed bytes
+fn signature_first(tx_type: Type) -> bool {
+    matches!(tx_type, Type::Issue | Type::Transfer | Type::Reissue)
+}
+
+/// Maximum number of proofs of a transaction
+pub const MAX_PROOFS: usize = 8;
+/// Maximum length of a proof in bytes
+pub const MAX_PROOF_LENGTH: usize = 64;
+
 /// Transaction with proofs. Proofs are byte vectors at most 64 bytes long, and maximum number of
 /// proofs is 8.
+///
+/// For accounts with a multisig script, collect the signatures of the owners at the indices the
+/// script expects, passing the partially signed transaction from one signer to the next, e.g. as
+/// JSON.
+/// # Usage
+/// ```
+/// use wavesplatform::account::{PrivateKeyAccount, TESTNET};
+/// use wavesplatform::transaction::{ProvenTransaction, Transaction};
+/// let multisig = PrivateKeyAccount::from_seed("multisig");
+/// let alice = PrivateKeyAccount::from_seed("alice");
+/// let bob = PrivateKeyAccount::from_seed("bob");
+///
+/// let tx = Transaction::new_alias(multisig.public_key(), "rhino", TESTNET, 500000, 1536000000000);
+/// let mut partially_signed = tx.with_proofs(vec![]);
+/// alice.sign_proof(&mut partially_signed, 0).unwrap();
+/// let json = serde_json::to_string(&partially_signed).unwrap();
+///
+/// let mut signed: ProvenTransaction = serde_json::from_str(&json).unwrap();
+/// bob.sign_proof(&mut signed, 2).unwrap();
+/// assert_eq!(signed.proofs.len(), 3);
+/// assert!(signed.proofs[1].is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
 pub struct ProvenTransaction<'a> {
     pub tx: Transaction<'a>,
     pub proofs: Vec<Vec<u8>>,
 }
 
+impl<'a> ProvenTransaction<'a> {
+    /// Sets the proof at the given index, adding empty proofs before it if there are fewer proofs.
+    /// Fails if the index is not less than [`MAX_PROOFS`] or the proof is longer than
+    /// [`MAX_PROOF_LENGTH`].
+    pub fn add_proof(&mut self, index: usize, proof: Vec<u8>) -> Result<(), Error> {
+        if index >= MAX_PROOFS {
+            return Err(Error::InvalidProofIndex(index));
+        }
+        if proof.len() > MAX_PROOF_LENGTH {
+            return Err(Error::InvalidProofLength(proof.len()));
+        }
+        if self.proofs.len() <= index {
+            self.proofs.resize(index + 1, Vec::new());
+        }
+        self.proofs[index] = proof;
+        Ok(())
+    }
+
+    /// Returns the indices of the proofs that are valid signatures of the transaction by its
+    /// sender.
+    /// ```
+    /// use wavesplatform::account::{PrivateKeyAccount, TESTNET};
+    /// use wavesplatform::transaction::Transaction;
+    /// let account = PrivateKeyAccount::from_seed("seed");
+    /// let tx = Transaction::new_alias(account.public_key(), "rhino", TESTNET, 100000, 1536000000000);
+    /// let signed_tx = account.sign_transaction(tx);
+    /// assert_eq!(signed_tx.verify(), vec![0]);
+    /// ```
+    pub fn verify(&self) -> Vec<usize> {
+        self.verify_with_keys(&[self.tx.sender_public_key])
+    }
+
+    /// Returns the indices of the proofs that are valid signatures of the transaction by any of
+    /// the given public keys, e.g. by the owners of a multisig account.
+    pub fn verify_with_keys(&self, public_keys: &[PublicKeyAccount]) -> Vec<usize> {
+        let body = self.tx.to_bytes();
+        self.proofs
+            .iter()
+            .enumerate()
+            .filter(|(_, proof)| {
+                <&[u8; SIGNATURE_LENGTH]>::try_from(proof.as_slice()).is_ok_and(|signature| {
+                    public_keys
+                        .iter()
+                        .any(|key| sig_verify(&body, &key.0, signature))
+                })
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Returns the signed transaction bytes: the legacy body followed by the proofs or, for
+    /// protobuf versions, the `SignedTransaction` message.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        if self.tx.is_protobuf() {
+            return self.to_protobuf();
+        }
+
+        let mut buf = Buffer::new();
+        if self.tx.is_signature_version() {
+            // version 1 has no leading zero and a single signature instead of the proofs, which
+            // precedes the body of Issue, Transfer and Reissue and follows that of other types
+            let signature = self.proofs.first().map(Vec::as_slice).unwrap_or_default();
+            if Type::try_from(self.tx.type_id).is_ok_and(signature_first) {
+                buf.byte(self.tx.type_id)
+                    .bytes(signature)
+                    .bytes(&self.tx.to_bytes());
+            } else {
+                buf.bytes(&self.tx.to_bytes()).bytes(signature);
+            }
+            return Vec::from(buf.as_slice());
+        }
+        // Mass Transfer has no leading zero, and the Exchange body already starts with one
+        if !matches!(self.tx.data, MassTransfer { .. } | Exchange { .. }) {
+            buf.byte(0);
+        }
+        buf.bytes(&self.tx.to_bytes()).proofs(&self.proofs);
+        Vec::from(buf.as_slice())
+    }
+
+    /// Parses a signed transaction, i.e. the bytes returned by [`ProvenTransaction::to_bytes`].
+    ///
+    /// Legacy signed transactions start with a zero byte, except for Mass Transfer which starts
+    /// with its type ID; anything else is parsed as a protobuf `SignedTransaction` or, failing
+    /// that, as a version 1 transaction with a single signature.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<ProvenTransaction<'a>, ParseError> {
+        let mut reader = Reader::new(bytes);
+        match reader.peek()? {
+            0 => {
+                reader.byte()?;
+            }
+            type_id if type_id == Type::MassTransfer as u8 => {}
+            _ => {
+                let result = ProvenTransaction::from_protobuf(bytes);
+                if result.is_err() && starts_with_signature_type(bytes) {
+                    if let Ok(tx) = ProvenTransaction::read_signature_version(bytes) {
+                        return Ok(tx);
+                    }
+                }
+                return result;
+            }
+        }
+        let tx = Transaction::read(&mut reader, false)?;
+        let proofs = reader.proofs()?;
+        reader.finish()?;
+        Ok(ProvenTransaction { tx, proofs })
+    }
+
+    /// Reads the signed bytes of a version 1 transaction, see [`ProvenTransaction::to_bytes`]
+    fn read_signature_version(bytes: &'a [u8]) -> Result<ProvenTransaction<'a>, ParseError> {
+        let mut reader = Reader::new(bytes);
+        let tx_type = Type::try_from(reader.peek()?)?;
+        let (tx, signature) = if signature_first(tx_type) {
+            reader.byte()?;
+            let signature = reader.bytes(SIGNATURE_LENGTH)?;
+            (Transaction::read(&mut reader, true)?, signature)
+        } else {
+            let tx = Transaction::read(&mut reader, true)?;
+            (tx, reader.bytes(SIGNATURE_LENGTH)?)
+        };
+        reader.finish()?;
+        Ok(ProvenTransaction {
+            tx,
+            proofs: vec![signature.to_vec()],
+        })
+    }
+
+    /// Converts the transaction to one that owns all its data, see [`Transaction::into_owned`].
+    pub fn into_owned(self) -> ProvenTransaction<'static> {
+        ProvenTransaction {
+            tx: self.tx.into_owned(),
+            proofs: self.proofs,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -481,18 +1301,18 @@ mod tests {
     use crate::account::{Address, PrivateKeyAccount, TESTNET};
 
     use base58::FromBase58;
-    use ed25519_dalek::*;
 
     #[test]
     fn test_tx_ids() {
         let pk = PublicKeyAccount([1u8; 32]);
         let asset = Asset::new([2u8; 32]);
         let lease = TransactionId::new([3u8; 32]);
-        let recipient = Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D");
+        let recipient =
+            Recipient::from(Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D").unwrap());
         let fee = 100000;
         let ts: u64 = 1536000000000;
 
-        fn check_hash(tx: &Transaction, hash: &str) -> () {
+        fn check_hash(tx: &Transaction, hash: &str) {
             assert_eq!(tx.id().to_bytes(), hash.from_base58().unwrap().as_slice());
         }
 
@@ -548,13 +1368,11 @@ mod tests {
         );
 
         let arr = vec![4u8; 32];
-        let bin_entry = DataEntry::Binary("bin", &arr);
-        let data = vec![
-            &DataEntry::Integer("int", 1),
-            &DataEntry::Boolean("bool", true),
-            &bin_entry,
-            &DataEntry::String("str", "str"),
-        ];
+        let int_entry = DataEntry::Integer("int".into(), 1);
+        let bool_entry = DataEntry::Boolean("bool".into(), true);
+        let bin_entry = DataEntry::Binary("bin".into(), (&arr).into());
+        let str_entry = DataEntry::String("str".into(), "str".into());
+        let data = vec![&int_entry, &bool_entry, &bin_entry, &str_entry];
         check_hash(
             &Transaction::new_data(&pk, data, fee, ts),
             "6fGLB7yxzkWPBb4fv32Fs7d5si6xifenj69Da9yHvwgx",
@@ -579,20 +1397,457 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tx_bytes_roundtrip() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let asset = Asset::new([2u8; 32]);
+        let lease = TransactionId::new([3u8; 32]);
+        let recipient =
+            Recipient::from(Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D").unwrap());
+        let fee = 100000;
+        let ts: u64 = 1536000000000;
+        let arr = vec![4u8; 32];
+        let int_entry = DataEntry::Integer("int".into(), 1);
+        let bin_entry = DataEntry::Binary("bin".into(), (&arr).into());
+        let script = vec![1, 6, 183, 111, 203, 71];
+
+        let txs = vec![
+            Transaction::new_issue(
+                &pk, "coin", "coin", 100000000, 8, false, TESTNET, fee, ts, None,
+            ),
+            Transaction::new_transfer(
+                &pk,
+                &recipient,
+                Some(&asset),
+                10,
+                None,
+                fee,
+                Some("atta ch me"),
+                ts,
+            ),
+            Transaction::new_reissue(&pk, &asset, 100000000, false, TESTNET, fee, ts),
+            Transaction::new_burn(&pk, &asset, 100000000, TESTNET, fee, ts),
+            Transaction::new_lease(&pk, &recipient, 10, TESTNET, fee, ts),
+            Transaction::new_lease_cancel(&pk, &lease, TESTNET, fee, ts),
+            Transaction::new_alias(&pk, "lilias", TESTNET, fee, ts),
+            Transaction::new_mass_transfer(
+                &pk,
+                Some(&asset),
+                vec![(&recipient, 10), (&recipient, 10)],
+                Some("mass trans"),
+                fee,
+                ts,
+            ),
+            Transaction::new_data(&pk, vec![&int_entry, &bin_entry], fee, ts),
+            Transaction::new_script(&pk, Some(script.as_slice()), TESTNET, fee, ts),
+            Transaction::new_script(&pk, None, TESTNET, fee, ts),
+            Transaction::new_sponsor(&pk, &asset, Some(100), fee, ts),
+            Transaction::new_set_asset_script(&pk, &asset, None, TESTNET, fee, ts),
+        ];
+        for tx in txs {
+            let bytes = tx.to_bytes();
+            let parsed = Transaction::from_bytes(&bytes).unwrap();
+            assert_eq!(parsed.id(), tx.id());
+            assert_eq!(parsed, tx);
+        }
+    }
+
+    #[test]
+    fn test_invoke_script_bytes() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let asset = Asset::new([2u8; 32]);
+        let dapp = Recipient::Alias {
+            alias: crate::util::Alias::new("merchant").unwrap(),
+            chain_id: TESTNET,
+        };
+        let call = FunctionCall::new(
+            "pay",
+            vec![
+                Arg::Integer(-1),
+                Arg::Binary(vec![4u8; 2].into()),
+                Arg::String("id".into()),
+                Arg::List(vec![Arg::Boolean(true), Arg::Boolean(false)]),
+            ],
+        );
+        let payments = vec![(None, 10), (Some(&asset), 20)];
+        let tx = Transaction::new_invoke_script(
+            &pk,
+            &dapp,
+            Some(&call),
+            payments,
+            Some(&asset),
+            500000,
+            1536000000000,
+        );
+
+        let bytes = tx.to_bytes();
+        let mut expected = vec![16, 1, TESTNET];
+        expected.extend_from_slice(&[1u8; 32]);
+        expected.extend_from_slice(&[2, TESTNET, 0, 8]);
+        expected.extend_from_slice(b"merchant");
+        expected.extend_from_slice(&[1, 9, 1, 0, 0, 0, 3]);
+        expected.extend_from_slice(b"pay");
+        expected.extend_from_slice(&[0, 0, 0, 4, 0, 255, 255, 255, 255, 255, 255, 255, 255]);
+        expected.extend_from_slice(&[1, 0, 0, 0, 2, 4, 4, 2, 0, 0, 0, 2]);
+        expected.extend_from_slice(b"id");
+        expected.extend_from_slice(&[11, 0, 0, 0, 2, 6, 7]);
+        assert_eq!(bytes[..expected.len()], expected[..]);
+
+        let parsed = Transaction::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, tx);
+        assert_eq!(parsed.id(), tx.id());
+
+        let dapp = Recipient::Address(pk.to_address(TESTNET));
+        let tx = Transaction::new_invoke_script(&pk, &dapp, None, vec![], None, 500000, 0);
+        assert_eq!(Transaction::from_bytes(&tx.to_bytes()).unwrap(), tx);
+    }
+
+    #[test]
+    fn test_nested_list_args() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let dapp = Recipient::Address(pk.to_address(TESTNET));
+        let nested = Arg::List(vec![Arg::List(vec![Arg::Integer(1)])]);
+        let call = FunctionCall::new("f", vec![nested]);
+        let tx = Transaction::new_invoke_script(&pk, &dapp, Some(&call), vec![], None, 500000, 0);
+        assert_eq!(
+            Transaction::from_bytes(&tx.to_bytes()),
+            Err(ParseError::UnsupportedExpression(11))
+        );
+    }
+
+    #[test]
+    fn test_exchange_bytes() {
+        let matcher = PrivateKeyAccount::from_seed("matcher");
+        let buyer = PrivateKeyAccount::from_seed("buyer");
+        let seller = PrivateKeyAccount::from_seed("seller");
+        let asset = Asset::new([2u8; 32]);
+        let order = |account: &PrivateKeyAccount, order_type, version| {
+            let order = Order::new(
+                account.public_key(),
+                matcher.public_key(),
+                Some(&asset),
+                None,
+                order_type,
+                100,
+                200,
+                1536000000000,
+                1538000000000,
+                300000,
+                None,
+                MAINNET,
+            );
+            account.sign_order(order.with_version(version))
+        };
+        let buy = order(&buyer, OrderType::Buy, Version::V1);
+        let sell = order(&seller, OrderType::Sell, Version::V3);
+        let tx = Transaction::new_exchange(
+            matcher.public_key(),
+            &buy,
+            &sell,
+            100,
+            200,
+            300000,
+            300000,
+            300000,
+            1536000000000,
+        );
+
+        let bytes = tx.to_bytes();
+        assert_eq!(bytes[..3], [0, 7, 2]);
+        assert_eq!(tx.id().to_bytes(), blake_hash(&bytes).as_slice());
+        assert_eq!(Transaction::from_bytes(&bytes), Ok(tx.clone()));
+
+        let signed = matcher.sign_transaction(tx);
+        let signed_bytes = signed.to_bytes();
+        assert_eq!(signed_bytes[..bytes.len()], bytes[..]);
+        assert_eq!(ProvenTransaction::from_bytes(&signed_bytes), Ok(signed));
+
+        let buy = order(&buyer, OrderType::Buy, Version::V4);
+        let tx = Transaction::new_exchange(
+            matcher.public_key(),
+            &buy,
+            &sell,
+            100,
+            200,
+            300000,
+            300000,
+            300000,
+            1536000000000,
+        );
+        assert_eq!(tx.version, Version::V3 as u8);
+        assert_eq!(Transaction::from_bytes(&tx.to_bytes()), Ok(tx));
+
+        let sell = order(&seller, OrderType::Sell, Version::V1);
+        let buy = order(&buyer, OrderType::Buy, Version::V1);
+        let tx = Transaction::new_exchange(
+            matcher.public_key(),
+            &buy,
+            &sell,
+            100,
+            200,
+            300000,
+            300000,
+            300000,
+            1536000000000,
+        )
+        .with_version(Version::V1);
+        let bytes = tx.to_bytes();
+        let (buy_bytes, sell_bytes) = (buy.to_bytes(), sell.to_bytes());
+        assert_eq!(bytes[0], 7);
+        assert_eq!(bytes[1..5], (buy_bytes.len() as u32).to_be_bytes());
+        assert_eq!(bytes[5..9], (sell_bytes.len() as u32).to_be_bytes());
+        assert_eq!(bytes[9..9 + buy_bytes.len()], buy_bytes[..]);
+        assert_eq!(Transaction::from_bytes(&bytes), Ok(tx.clone()));
+
+        let signed = matcher.sign_transaction(tx);
+        let signed_bytes = signed.to_bytes();
+        assert_eq!(signed_bytes[..bytes.len()], bytes[..]);
+        assert_eq!(signed_bytes.len(), bytes.len() + SIGNATURE_LENGTH);
+        assert_eq!(ProvenTransaction::from_bytes(&signed_bytes), Ok(signed));
+    }
+
+    #[test]
+    fn test_signature_version_bytes() {
+        let account = PrivateKeyAccount::from_seed("test");
+        let pk = account.public_key();
+        let asset = Asset::new([2u8; 32]);
+        let lease = TransactionId::new([3u8; 32]);
+        let recipient =
+            Recipient::from(Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D").unwrap());
+        let fee = 100000;
+        let ts: u64 = 1536000000000;
+
+        // version 1 bodies have no chain ID, so the chain of parsed transactions is MAINNET
+        // unless a recipient or the alias has one
+        let txs = vec![
+            Transaction::new_issue(
+                pk, "coin", "coin", 100000000, 8, false, MAINNET, fee, ts, None,
+            ),
+            Transaction::new_transfer(pk, &recipient, Some(&asset), 10, None, fee, None, ts),
+            Transaction::new_reissue(pk, &asset, 100000000, false, MAINNET, fee, ts),
+            Transaction::new_burn(pk, &asset, 100000000, MAINNET, fee, ts),
+            Transaction::new_lease(pk, &recipient, 10, TESTNET, fee, ts),
+            Transaction::new_lease_cancel(pk, &lease, MAINNET, fee, ts),
+            Transaction::new_alias(pk, "lilias", TESTNET, fee, ts),
+        ];
+        for tx in txs {
+            let v2_body = tx.to_bytes();
+            let tx = tx.with_version(Version::V1);
+            let body = tx.to_bytes();
+            assert_eq!(body[0], tx.type_id);
+            assert_eq!(body[1..33], pk.to_bytes()[..]);
+            // the version 2 body adds the version and a chain ID or asset byte, except for
+            // Transfer and Alias, and an empty script to Issue
+            let extra = match tx.data {
+                Transfer { .. } | Alias { .. } => 1,
+                Issue { .. } => 3,
+                _ => 2,
+            };
+            assert_eq!(body.len() + extra, v2_body.len());
+            if !matches!(tx.data, Alias { .. }) {
+                assert_eq!(tx.id().to_bytes(), blake_hash(&body).as_slice());
+            }
+            assert_eq!(Transaction::from_bytes(&body), Ok(tx.clone()));
+
+            let signed = account.sign_transaction(tx.clone());
+            assert_eq!(signed.verify(), vec![0]);
+            let signed_bytes = signed.to_bytes();
+            if matches!(tx.data, Issue { .. } | Transfer { .. } | Reissue { .. }) {
+                // the type ID, the signature and the body, which repeats the type ID
+                assert_eq!(signed_bytes[0], tx.type_id);
+                assert_eq!(signed_bytes[1..65], signed.proofs[0][..]);
+                assert_eq!(signed_bytes[65..], body[..]);
+            } else {
+                assert_eq!(signed_bytes[..body.len()], body[..]);
+                assert_eq!(signed_bytes[body.len()..], signed.proofs[0][..]);
+            }
+            assert_eq!(ProvenTransaction::from_bytes(&signed_bytes), Ok(signed));
+        }
+    }
+
+    #[test]
+    fn test_multisig_proofs() {
+        let multisig = PrivateKeyAccount::from_seed("multisig");
+        let owners: Vec<_> = ["alice", "bob", "carol"]
+            .iter()
+            .map(|seed| PrivateKeyAccount::from_seed(seed))
+            .collect();
+        let tx = Transaction::new_alias(&multisig.1, "rhino", TESTNET, 500000, 1500000000000);
+        let mut ptx = tx.with_proofs(vec![]);
+
+        owners[2].sign_proof(&mut ptx, 2).unwrap();
+        let bytes = ptx.to_bytes();
+        let mut ptx = ProvenTransaction::from_bytes(&bytes).unwrap();
+        owners[0].sign_proof(&mut ptx, 0).unwrap();
+
+        assert_eq!(ptx.proofs.len(), 3);
+        assert!(ptx.proofs[1].is_empty());
+        let body = ptx.tx.to_bytes();
+        for index in [0, 2] {
+            let mut sig = [0u8; SIGNATURE_LENGTH];
+            sig.copy_from_slice(&ptx.proofs[index]);
+            assert!(sig_verify(&body, &owners[index].1 .0, &sig));
+        }
+
+        assert!(matches!(
+            ptx.add_proof(MAX_PROOFS, vec![1]),
+            Err(Error::InvalidProofIndex(8))
+        ));
+        assert!(matches!(
+            ptx.add_proof(1, vec![1; 65]),
+            Err(Error::InvalidProofLength(65))
+        ));
+        ptx.add_proof(7, vec![1; 64]).unwrap();
+        assert_eq!(ptx.proofs.len(), MAX_PROOFS);
+
+        let keys: Vec<_> = owners.iter().map(|owner| owner.1).collect();
+        assert_eq!(ptx.verify_with_keys(&keys), vec![0, 2]);
+        assert_eq!(ptx.verify_with_keys(&keys[1..]), vec![2]);
+        assert!(ptx.verify().is_empty());
+    }
+
+    #[test]
+    fn test_verify() {
+        let sender = PrivateKeyAccount::from_seed("test");
+        let tx = Transaction::new_alias(&sender.1, "rhino", TESTNET, 100000, 1500000000000);
+        let mut ptx = sender.sign_transaction(tx);
+        assert_eq!(ptx.verify(), vec![0]);
+
+        ptx.add_proof(1, vec![1, 2, 3]).unwrap();
+        ptx.add_proof(2, vec![0; 64]).unwrap();
+        let signature = ptx.proofs[0].clone();
+        ptx.add_proof(3, signature).unwrap();
+        assert_eq!(ptx.verify(), vec![0, 3]);
+
+        // a public key that is not a valid curve point must not make verification panic
+        let mut invalid_key = [0xffu8; 32];
+        invalid_key[0] = 0xec;
+        invalid_key[31] = 0x7f;
+        assert!(ptx
+            .verify_with_keys(&[PublicKeyAccount(invalid_key)])
+            .is_empty());
+
+        ptx.tx = ptx.tx.with_fee(200000);
+        assert!(ptx.verify().is_empty());
+    }
+
     #[test]
     fn test_sign() {
         let sender = PrivateKeyAccount::from_seed("test");
-        let recipient = Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D");
+        let recipient =
+            Recipient::from(Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D").unwrap());
         let tx = Transaction::new_lease(&sender.1, &recipient, 100000, 84, 100000, 1500000000000);
 
         let ProvenTransaction { tx, proofs } = sender.sign_transaction(tx);
         assert_eq!(proofs.len(), 1);
-        let sig = proofs.get(0).unwrap();
+        let sig = proofs.first().unwrap();
         assert_eq!(sig.len(), SIGNATURE_LENGTH);
 
         let ProvenTransaction { tx: _, proofs } = tx.with_proofs(vec![vec![1, 2, 3]]);
         assert_eq!(proofs.len(), 1);
-        let sig = proofs.get(0).unwrap();
+        let sig = proofs.first().unwrap();
         assert_eq!(*sig, vec![1, 2, 3]);
     }
+
+    #[test]
+    fn test_proven_tx_bytes_roundtrip() {
+        let sender = PrivateKeyAccount::from_seed("test");
+        let recipient =
+            Recipient::from(Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D").unwrap());
+
+        let tx = Transaction::new_lease(&sender.1, &recipient, 100000, 84, 100000, 1500000000000);
+        let signed = sender.sign_transaction(tx);
+        let bytes = signed.to_bytes();
+        assert_eq!(bytes[0], 0);
+        assert_eq!(ProvenTransaction::from_bytes(&bytes).unwrap(), signed);
+
+        let transfers = vec![(&recipient, 10)];
+        let tx =
+            Transaction::new_mass_transfer(&sender.1, None, transfers, None, 100000, 1500000000000);
+        let signed = tx.with_proofs(vec![vec![1, 2, 3], vec![]]);
+        let bytes = signed.to_bytes();
+        assert_eq!(bytes[0], Type::MassTransfer as u8);
+        assert_eq!(ProvenTransaction::from_bytes(&bytes).unwrap(), signed);
+        let alias = Recipient::Alias {
+            alias: crate::util::Alias::new("merchant").unwrap(),
+            chain_id: 84,
+        };
+        let tx = Transaction::new_transfer(
+            &sender.1,
+            &alias,
+            None,
+            10,
+            None,
+            100000,
+            None,
+            1500000000000,
+        );
+        let signed = sender.sign_transaction(tx);
+        assert_eq!(
+            ProvenTransaction::from_bytes(&signed.to_bytes()).unwrap(),
+            signed
+        );
+    }
+
+    #[test]
+    fn test_into_owned() {
+        let sender = PrivateKeyAccount::from_seed("test");
+        let entry = DataEntry::String("key".into(), "value".into());
+        let (bytes, signed) = {
+            let tx = Transaction::new_data(&sender.1, vec![&entry], 100000, 1500000000000);
+            let signed = sender.sign_transaction(tx);
+            (signed.to_bytes(), signed.clone().into_owned())
+        };
+
+        let parsed = ProvenTransaction::from_bytes(&bytes).unwrap().into_owned();
+        drop(bytes);
+        let handle = std::thread::spawn(move || parsed);
+        assert_eq!(handle.join().unwrap(), signed);
+    }
+
+    #[test]
+    fn test_tx_from_bytes_errors() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let bytes =
+            Transaction::new_alias(&pk, "lilias", TESTNET, 100000, 1536000000000).to_bytes();
+
+        assert_eq!(
+            Transaction::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(ParseError::UnexpectedEnd)
+        );
+        assert_eq!(Transaction::from_bytes(&[]), Err(ParseError::UnexpectedEnd));
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            Transaction::from_bytes(&trailing),
+            Err(ParseError::TrailingBytes(1))
+        );
+
+        let mut unknown = bytes.clone();
+        unknown[0] = 2;
+        assert_eq!(
+            Transaction::from_bytes(&unknown),
+            Err(ParseError::UnknownType(2))
+        );
+
+        let mut unsupported = bytes.clone();
+        unsupported[1] = 1;
+        assert_eq!(
+            Transaction::from_bytes(&unsupported),
+            Err(ParseError::UnsupportedVersion(10, 1))
+        );
+
+        let mut proven = ProvenTransaction {
+            tx: Transaction::from_bytes(&bytes).unwrap(),
+            proofs: vec![],
+        }
+        .to_bytes();
+        let proofs_version = proven.len() - 3;
+        proven[proofs_version] = 2;
+        assert_eq!(
+            ProvenTransaction::from_bytes(&proven),
+            Err(ParseError::UnsupportedProofsVersion(2))
+        );
+    }
 }
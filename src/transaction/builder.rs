@@ -0,0 +1,774 @@
+use crate::account::PublicKeyAccount;
+use crate::error::Error;
+use crate::fee::min_fee;
+use crate::transaction::{
+    latest_version, legacy_version, Asset, DataEntry, FunctionCall, ProvenOrder, Recipient,
+    Transaction, TransactionData, TransactionId, Type, Version,
+};
+use crate::util;
+
+use std::borrow::Cow;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use TransactionData::*;
+
+const MAX_ATTACHMENT_LENGTH: usize = 140;
+const MAX_TRANSFERS: usize = 100;
+const MAX_DATA_ENTRIES: usize = 100;
+const MAX_PAYMENTS: usize = 10;
+
+/// Fields every transaction has, with their defaults applied in `build()`
+struct Common<'a> {
+    sender_public_key: &'a PublicKeyAccount,
+    chain_id: Option<u8>,
+    fee: Option<u64>,
+    timestamp: Option<u64>,
+    version: Option<Version>,
+}
+
+impl<'a> Common<'a> {
+    fn new(sender_public_key: &'a PublicKeyAccount) -> Common<'a> {
+        Common {
+            sender_public_key,
+            chain_id: None,
+            fee: None,
+            timestamp: None,
+            version: None,
+        }
+    }
+
+    fn build(
+        self,
+        tx_type: Type,
+        data: TransactionData<'a>,
+        default_chain_id: Option<u8>,
+    ) -> Result<Transaction<'a>, Error> {
+        let version = self.version.unwrap_or_else(|| latest_version(tx_type));
+        let legacy = legacy_version(tx_type);
+        let supported = Some(version) == legacy
+            || (legacy.is_none_or(|legacy| version as u8 > legacy as u8)
+                && version as u8 <= latest_version(tx_type) as u8);
+        if !supported {
+            return Err(Error::InvalidField("version"));
+        }
+
+        let mut tx = Transaction {
+            data,
+            fee: 0,
+            timestamp: self.timestamp.unwrap_or_else(now),
+            sender_public_key: *self.sender_public_key,
+            chain_id: required(self.chain_id.or(default_chain_id), "chain_id")?,
+            type_id: tx_type as u8,
+            version: version as u8,
+        };
+        // the minimum fee is in WAVELET, so a fee in a sponsored asset must be set explicitly
+        let sponsored = matches!(
+            tx.data,
+            Transfer {
+                fee_asset: Some(_),
+                ..
+            } | InvokeScript {
+                fee_asset: Some(_),
+                ..
+            }
+        );
+        tx.fee = match self.fee {
+            Some(fee) => fee,
+            None if sponsored => return Err(Error::MissingField("fee")),
+            None => min_fee(&tx),
+        };
+        Ok(tx)
+    }
+}
+
+/// Current time in milliseconds, the default transaction timestamp
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn required<T>(value: Option<T>, field: &'static str) -> Result<T, Error> {
+    value.ok_or(Error::MissingField(field))
+}
+
+fn check(valid: bool, field: &'static str) -> Result<(), Error> {
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidField(field))
+    }
+}
+
+fn check_asset_info(name: &str, description: &str) -> Result<(), Error> {
+    check((4..=16).contains(&name.len()), "name")?;
+    check(description.len() <= 1000, "description")
+}
+
+fn check_attachment(attachment: Option<&str>) -> Result<(), Error> {
+    check(
+        attachment.is_none_or(|a| a.len() <= MAX_ATTACHMENT_LENGTH),
+        "attachment",
+    )
+}
+
+macro_rules! builder {
+    (
+        $(#[$doc:meta])*
+        struct $name:ident {
+            $(
+                $(#[$field_doc:meta])*
+                $field:ident: $field_type:ty,
+            )*
+        }
+    ) => {
+        $(#[$doc])*
+        ///
+        /// The timestamp defaults to the current time, the fee to the minimum fee of the
+        /// transaction and the version to the latest one supported. The chain ID defaults to the
+        /// one of the recipient and is required for transactions without a recipient. Required
+        /// fields are checked by `build()`.
+        pub struct $name<'a> {
+            common: Common<'a>,
+            $( $field: Option<$field_type>, )*
+        }
+
+        impl<'a> $name<'a> {
+            /// Creates a builder for a transaction of the given sender
+            pub fn new(sender_public_key: &'a PublicKeyAccount) -> $name<'a> {
+                $name {
+                    common: Common::new(sender_public_key),
+                    $( $field: None, )*
+                }
+            }
+
+            $(
+                $(#[$field_doc])*
+                pub fn $field(mut self, $field: $field_type) -> $name<'a> {
+                    self.$field = Some($field);
+                    self
+                }
+            )*
+
+            /// Sets the chain ID
+            pub fn chain_id(mut self, chain_id: u8) -> $name<'a> {
+                self.common.chain_id = Some(chain_id);
+                self
+            }
+
+            /// Sets the fee instead of the minimum fee, in WAVELET or in the fee asset if it is set
+            pub fn fee(mut self, fee: u64) -> $name<'a> {
+                self.common.fee = Some(fee);
+                self
+            }
+
+            /// Sets the timestamp in milliseconds instead of the current time
+            pub fn timestamp(mut self, timestamp: u64) -> $name<'a> {
+                self.common.timestamp = Some(timestamp);
+                self
+            }
+
+            /// Sets the version instead of the latest one
+            pub fn version(mut self, version: Version) -> $name<'a> {
+                self.common.version = Some(version);
+                self
+            }
+        }
+    };
+}
+
+builder! {
+    /// Builder of Issue transactions.
+    /// # Usage
+    /// ```
+    /// use wavesplatform::account::{PrivateKeyAccount, TESTNET};
+    /// use wavesplatform::transaction::IssueBuilder;
+    /// let account = PrivateKeyAccount::from_seed("seed");
+    /// let tx = IssueBuilder::new(account.public_key())
+    ///     .name("Token")
+    ///     .quantity(1000000)
+    ///     .decimals(2)
+    ///     .chain_id(TESTNET)
+    ///     .build()
+    ///     .unwrap();
+    /// let signed_tx = account.sign_transaction(tx);
+    /// ```
+    struct IssueBuilder {
+        /// Sets the asset name, 4 to 16 bytes
+        name: &'a str,
+        /// Sets the asset description, at most 1000 bytes; empty by default
+        description: &'a str,
+        /// Sets the amount of the asset in its minimal units
+        quantity: u64,
+        /// Sets the number of decimals, at most 8; 8 by default
+        decimals: u8,
+        /// Sets whether the asset can be reissued; `false` by default
+        reissuable: bool,
+        /// Sets the compiled asset script
+        script: &'a [u8],
+    }
+}
+
+impl<'a> IssueBuilder<'a> {
+    /// Validates the fields and creates the transaction. The chain ID is required.
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let name = required(self.name, "name")?;
+        let description = self.description.unwrap_or("");
+        check_asset_info(name, description)?;
+        let decimals = self.decimals.unwrap_or(8);
+        check(decimals <= 8, "decimals")?;
+        let data = Issue {
+            name: name.into(),
+            description: description.into(),
+            quantity: required(self.quantity, "quantity")?,
+            decimals,
+            reissuable: self.reissuable.unwrap_or(false),
+            script: self.script.map(Cow::from),
+        };
+        self.common.build(Type::Issue, data, None)
+    }
+}
+
+builder! {
+    /// Builder of Transfer transactions.
+    /// # Usage
+    /// ```
+    /// use wavesplatform::account::PrivateKeyAccount;
+    /// use wavesplatform::transaction::{Recipient, TransferBuilder};
+    /// let account = PrivateKeyAccount::from_seed("seed");
+    /// let recipient = Recipient::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D").unwrap();
+    /// let tx = TransferBuilder::new(account.public_key())
+    ///     .recipient(&recipient)
+    ///     .amount(100000000)
+    ///     .attachment("thanks")
+    ///     .build()
+    ///     .unwrap();
+    /// let signed_tx = account.sign_transaction(tx);
+    /// ```
+    struct TransferBuilder {
+        /// Sets the recipient, which also sets the default chain ID
+        recipient: &'a Recipient,
+        /// Sets the transferred asset; WAVES by default
+        asset: &'a Asset,
+        /// Sets the amount of the asset in its minimal units
+        amount: u64,
+        /// Sets the sponsored asset to pay the fee in; WAVES by default. The fee is then required,
+        /// in the minimal units of the asset, see [`crate::fee::to_sponsored_asset`].
+        fee_asset: &'a Asset,
+        /// Sets the attachment, at most 140 bytes
+        attachment: &'a str,
+    }
+}
+
+impl<'a> TransferBuilder<'a> {
+    /// Validates the fields and creates the transaction
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let recipient = required(self.recipient, "recipient")?;
+        check_attachment(self.attachment)?;
+        let data = Transfer {
+            recipient: recipient.clone(),
+            asset: self.asset.copied(),
+            amount: required(self.amount, "amount")?,
+            fee_asset: self.fee_asset.copied(),
+            attachment: self.attachment.map(|s| s.as_bytes().into()),
+        };
+        self.common
+            .build(Type::Transfer, data, Some(recipient.chain_id()))
+    }
+}
+
+builder! {
+    /// Builder of Reissue transactions
+    struct ReissueBuilder {
+        /// Sets the reissued asset
+        asset: &'a Asset,
+        /// Sets the amount to add in the minimal units of the asset
+        quantity: u64,
+        /// Sets whether the asset can be reissued again; `false` by default
+        reissuable: bool,
+    }
+}
+
+impl<'a> ReissueBuilder<'a> {
+    /// Validates the fields and creates the transaction. The chain ID is required.
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let data = Reissue {
+            asset: *required(self.asset, "asset")?,
+            quantity: required(self.quantity, "quantity")?,
+            reissuable: self.reissuable.unwrap_or(false),
+        };
+        self.common.build(Type::Reissue, data, None)
+    }
+}
+
+builder! {
+    /// Builder of Burn transactions
+    struct BurnBuilder {
+        /// Sets the burned asset
+        asset: &'a Asset,
+        /// Sets the amount to burn in the minimal units of the asset
+        quantity: u64,
+    }
+}
+
+impl<'a> BurnBuilder<'a> {
+    /// Validates the fields and creates the transaction. The chain ID is required.
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let data = Burn {
+            asset: *required(self.asset, "asset")?,
+            quantity: required(self.quantity, "quantity")?,
+        };
+        self.common.build(Type::Burn, data, None)
+    }
+}
+
+builder! {
+    /// Builder of Exchange transactions. The sender is the matcher.
+    struct ExchangeBuilder {
+        /// Sets the first order
+        order1: &'a ProvenOrder,
+        /// Sets the second order
+        order2: &'a ProvenOrder,
+        /// Sets the amount of the amount asset
+        amount: u64,
+        /// Sets the price of the amount asset in the price asset
+        price: u64,
+        /// Sets the matcher fee paid by the buyer
+        buy_matcher_fee: u64,
+        /// Sets the matcher fee paid by the seller
+        sell_matcher_fee: u64,
+    }
+}
+
+impl<'a> ExchangeBuilder<'a> {
+    /// Validates the fields and creates the transaction on the chain of the first order. Orders
+    /// of version 4 require the transaction version 3.
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let order1 = required(self.order1, "order1")?;
+        let order2 = required(self.order2, "order2")?;
+        let has_v4_order = order1.order.version >= 4 || order2.order.version >= 4;
+        check(
+            !has_v4_order || self.common.version.is_none_or(|v| v as u8 >= 3),
+            "version",
+        )?;
+        let data = Exchange {
+            order1: order1.clone(),
+            order2: order2.clone(),
+            amount: required(self.amount, "amount")?,
+            price: required(self.price, "price")?,
+            buy_matcher_fee: required(self.buy_matcher_fee, "buy_matcher_fee")?,
+            sell_matcher_fee: required(self.sell_matcher_fee, "sell_matcher_fee")?,
+        };
+        self.common
+            .build(Type::Exchange, data, Some(order1.order.chain_id))
+    }
+}
+
+builder! {
+    /// Builder of Lease transactions
+    struct LeaseBuilder {
+        /// Sets the recipient of the lease, which also sets the default chain ID
+        recipient: &'a Recipient,
+        /// Sets the leased amount in WAVELET
+        amount: u64,
+    }
+}
+
+impl<'a> LeaseBuilder<'a> {
+    /// Validates the fields and creates the transaction
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let recipient = required(self.recipient, "recipient")?;
+        let data = Lease {
+            recipient: recipient.clone(),
+            amount: required(self.amount, "amount")?,
+        };
+        self.common
+            .build(Type::Lease, data, Some(recipient.chain_id()))
+    }
+}
+
+builder! {
+    /// Builder of Lease Cancel transactions
+    struct LeaseCancelBuilder {
+        /// Sets the ID of the Lease transaction to cancel
+        lease_id: &'a TransactionId,
+    }
+}
+
+impl<'a> LeaseCancelBuilder<'a> {
+    /// Validates the fields and creates the transaction. The chain ID is required.
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let data = CancelLease {
+            lease_id: *required(self.lease_id, "lease_id")?,
+        };
+        self.common.build(Type::LeaseCancel, data, None)
+    }
+}
+
+builder! {
+    /// Builder of Create Alias transactions
+    struct AliasBuilder {
+        /// Sets the alias without the `alias:<chain ID>:` prefix
+        alias: &'a str,
+    }
+}
+
+impl<'a> AliasBuilder<'a> {
+    /// Validates the fields and creates the transaction. The chain ID is required.
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let alias = required(self.alias, "alias")?;
+        check(!alias.starts_with("alias:"), "alias")?;
+        util::Alias::new(alias).map_err(|_| Error::InvalidAlias)?;
+        let data = Alias {
+            alias: alias.into(),
+        };
+        self.common.build(Type::Alias, data, None)
+    }
+}
+
+builder! {
+    /// Builder of Mass Transfer transactions
+    struct MassTransferBuilder {
+        /// Sets the transferred asset; WAVES by default
+        asset: &'a Asset,
+        /// Sets all transfers, at most 100
+        transfers: Vec<(&'a Recipient, u64)>,
+        /// Sets the attachment, at most 140 bytes
+        attachment: &'a str,
+    }
+}
+
+impl<'a> MassTransferBuilder<'a> {
+    /// Adds a transfer of `amount` to the recipient
+    pub fn transfer(mut self, recipient: &'a Recipient, amount: u64) -> MassTransferBuilder<'a> {
+        self.transfers
+            .get_or_insert_with(Vec::new)
+            .push((recipient, amount));
+        self
+    }
+
+    /// Validates the fields and creates the transaction on the chain of the first recipient, unless
+    /// the chain ID is set
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let transfers = required(self.transfers, "transfers")?;
+        check(transfers.len() <= MAX_TRANSFERS, "transfers")?;
+        check_attachment(self.attachment)?;
+        let chain_id = transfers.first().map(|(recipient, _)| recipient.chain_id());
+        let data = MassTransfer {
+            asset: self.asset.copied(),
+            transfers: transfers
+                .into_iter()
+                .map(|(recipient, amount)| (recipient.clone(), amount))
+                .collect(),
+            attachment: self.attachment.map(|s| s.as_bytes().into()),
+        };
+        self.common.build(Type::MassTransfer, data, chain_id)
+    }
+}
+
+builder! {
+    /// Builder of Data transactions
+    struct DataBuilder {
+        /// Sets all entries, at most 100
+        data: Vec<&'a DataEntry<'a>>,
+    }
+}
+
+impl<'a> DataBuilder<'a> {
+    /// Adds an entry
+    pub fn entry(mut self, entry: &'a DataEntry<'a>) -> DataBuilder<'a> {
+        self.data.get_or_insert_with(Vec::new).push(entry);
+        self
+    }
+
+    /// Validates the fields and creates the transaction. The chain ID is required.
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let data = required(self.data, "data")?;
+        check(data.len() <= MAX_DATA_ENTRIES, "data")?;
+        // only protobuf versions can delete entries
+        let deletes = data
+            .iter()
+            .any(|entry| matches!(entry, DataEntry::Delete(_)));
+        check(!deletes || self.common.version != Some(Version::V1), "data")?;
+        let data = Data {
+            data: data.into_iter().cloned().collect(),
+        };
+        self.common.build(Type::Data, data, None)
+    }
+}
+
+builder! {
+    /// Builder of Set Script transactions. Without a script, the transaction removes the account
+    /// script.
+    struct SetScriptBuilder {
+        /// Sets the compiled account script
+        script: &'a [u8],
+    }
+}
+
+impl<'a> SetScriptBuilder<'a> {
+    /// Creates the transaction. The chain ID is required.
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let data = SetScript {
+            script: self.script.map(Cow::from),
+        };
+        self.common.build(Type::SetScript, data, None)
+    }
+}
+
+builder! {
+    /// Builder of Sponsor Fee transactions. Without a rate, the transaction cancels the
+    /// sponsorship.
+    struct SponsorBuilder {
+        /// Sets the sponsored asset
+        asset: &'a Asset,
+        /// Sets the amount of the asset that is equivalent to 0.001 WAVES
+        rate: u64,
+    }
+}
+
+impl<'a> SponsorBuilder<'a> {
+    /// Validates the fields and creates the transaction. The chain ID is required.
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let data = Sponsor {
+            asset: *required(self.asset, "asset")?,
+            rate: self.rate,
+        };
+        self.common.build(Type::Sponsor, data, None)
+    }
+}
+
+builder! {
+    /// Builder of Set Asset Script transactions
+    struct SetAssetScriptBuilder {
+        /// Sets the asset
+        asset: &'a Asset,
+        /// Sets the compiled asset script
+        script: &'a [u8],
+    }
+}
+
+impl<'a> SetAssetScriptBuilder<'a> {
+    /// Validates the fields and creates the transaction. The chain ID is required.
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let data = SetAssetScript {
+            asset: *required(self.asset, "asset")?,
+            script: Some(required(self.script, "script")?.into()),
+        };
+        self.common.build(Type::SetAssetScript, data, None)
+    }
+}
+
+builder! {
+    /// Builder of Invoke Script transactions. Without a function call, the transaction invokes
+    /// the default function of the dApp.
+    struct InvokeScriptBuilder {
+        /// Sets the dApp, which also sets the default chain ID
+        dapp: &'a Recipient,
+        /// Sets the called function
+        call: &'a FunctionCall<'a>,
+        /// Sets all payments, at most 10; `None` stands for WAVES
+        payments: Vec<(Option<&'a Asset>, u64)>,
+        /// Sets the sponsored asset to pay the fee in; WAVES by default. The fee is then required,
+        /// in the minimal units of the asset, see [`crate::fee::to_sponsored_asset`].
+        fee_asset: &'a Asset,
+    }
+}
+
+impl<'a> InvokeScriptBuilder<'a> {
+    /// Adds a payment of `amount` of the asset, WAVES if `None`
+    pub fn payment(mut self, asset: Option<&'a Asset>, amount: u64) -> InvokeScriptBuilder<'a> {
+        self.payments
+            .get_or_insert_with(Vec::new)
+            .push((asset, amount));
+        self
+    }
+
+    /// Validates the fields and creates the transaction
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let dapp = required(self.dapp, "dapp")?;
+        let payments = self.payments.unwrap_or_default();
+        check(payments.len() <= MAX_PAYMENTS, "payments")?;
+        let data = InvokeScript {
+            dapp: dapp.clone(),
+            call: self.call.cloned(),
+            payments: payments
+                .into_iter()
+                .map(|(asset, amount)| (asset.copied(), amount))
+                .collect(),
+            fee_asset: self.fee_asset.copied(),
+        };
+        self.common
+            .build(Type::InvokeScript, data, Some(dapp.chain_id()))
+    }
+}
+
+builder! {
+    /// Builder of Update Asset Info transactions
+    struct UpdateAssetInfoBuilder {
+        /// Sets the asset
+        asset: &'a Asset,
+        /// Sets the new asset name, 4 to 16 bytes
+        name: &'a str,
+        /// Sets the new asset description, at most 1000 bytes
+        description: &'a str,
+    }
+}
+
+impl<'a> UpdateAssetInfoBuilder<'a> {
+    /// Validates the fields and creates the transaction. The chain ID is required.
+    pub fn build(self) -> Result<Transaction<'a>, Error> {
+        let name = required(self.name, "name")?;
+        let description = required(self.description, "description")?;
+        check_asset_info(name, description)?;
+        let data = UpdateAssetInfo {
+            asset: *required(self.asset, "asset")?,
+            name: name.into(),
+            description: description.into(),
+        };
+        self.common.build(Type::UpdateAssetInfo, data, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::{Address, TESTNET};
+
+    #[test]
+    fn test_transfer_builder() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let recipient =
+            Recipient::from(Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D").unwrap());
+        let before = now();
+        let tx = TransferBuilder::new(&pk)
+            .recipient(&recipient)
+            .amount(10)
+            .build()
+            .unwrap();
+        assert_eq!(tx.version, Version::V3 as u8);
+        assert_eq!(tx.fee, 100000);
+        assert_eq!(tx.chain_id, TESTNET);
+        assert!(tx.timestamp >= before && tx.timestamp <= now());
+
+        let tx = TransferBuilder::new(&pk)
+            .recipient(&recipient)
+            .amount(10)
+            .fee(200000)
+            .timestamp(1536000000000)
+            .version(Version::V2)
+            .build()
+            .unwrap();
+        let expected =
+            Transaction::new_transfer(&pk, &recipient, None, 10, None, 200000, None, 1536000000000);
+        assert_eq!(tx, expected);
+
+        assert!(matches!(
+            TransferBuilder::new(&pk).amount(10).build(),
+            Err(Error::MissingField("recipient"))
+        ));
+
+        let asset = Asset::new([2u8; 32]);
+        let sponsored = TransferBuilder::new(&pk)
+            .recipient(&recipient)
+            .amount(10)
+            .fee_asset(&asset);
+        assert!(matches!(sponsored.build(), Err(Error::MissingField("fee"))));
+        let fee = crate::fee::to_sponsored_asset(100000, 5);
+        let tx = TransferBuilder::new(&pk)
+            .recipient(&recipient)
+            .amount(10)
+            .fee_asset(&asset)
+            .fee(fee)
+            .build()
+            .unwrap();
+        assert_eq!(tx.fee, 5);
+        assert!(matches!(
+            InvokeScriptBuilder::new(&pk)
+                .dapp(&recipient)
+                .fee_asset(&asset)
+                .build(),
+            Err(Error::MissingField("fee"))
+        ));
+        assert!(matches!(
+            TransferBuilder::new(&pk)
+                .recipient(&recipient)
+                .amount(10)
+                .version(Version::V1)
+                .build(),
+            Err(Error::InvalidField("version"))
+        ));
+    }
+
+    #[test]
+    fn test_min_fees() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let issue = IssueBuilder::new(&pk)
+            .name("Token")
+            .quantity(1000)
+            .chain_id(TESTNET);
+        assert_eq!(issue.build().unwrap().fee, 100000000);
+        let nft = IssueBuilder::new(&pk)
+            .name("Token")
+            .quantity(1)
+            .decimals(0)
+            .chain_id(TESTNET);
+        assert_eq!(nft.build().unwrap().fee, 100000);
+
+        let recipient = Recipient::from(pk.to_address(TESTNET));
+        let mut mass_transfer = MassTransferBuilder::new(&pk);
+        for _ in 0..3 {
+            mass_transfer = mass_transfer.transfer(&recipient, 10);
+        }
+        let tx = mass_transfer.build().unwrap();
+        assert_eq!(tx.fee, 300000);
+        assert_eq!(tx.version, Version::V2 as u8);
+
+        let value = vec![0u8; 1500];
+        let entry = DataEntry::Binary("key".into(), (&value).into());
+        let tx = DataBuilder::new(&pk)
+            .entry(&entry)
+            .chain_id(TESTNET)
+            .build()
+            .unwrap();
+        assert_eq!(tx.fee, 200000);
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        assert!(matches!(
+            IssueBuilder::new(&pk).name("T").quantity(1).build(),
+            Err(Error::InvalidField("name"))
+        ));
+        assert!(matches!(
+            AliasBuilder::new(&pk).alias("A").build(),
+            Err(Error::InvalidAlias)
+        ));
+        assert!(matches!(
+            MassTransferBuilder::new(&pk).build(),
+            Err(Error::MissingField("transfers"))
+        ));
+        let delete = DataEntry::Delete("key".into());
+        assert!(matches!(
+            DataBuilder::new(&pk)
+                .entry(&delete)
+                .version(Version::V1)
+                .build(),
+            Err(Error::InvalidField("data"))
+        ));
+        let data = DataBuilder::new(&pk).entry(&delete);
+        assert!(matches!(data.build(), Err(Error::MissingField("chain_id"))));
+        let data = DataBuilder::new(&pk).entry(&delete).chain_id(TESTNET);
+        assert!(data.build().is_ok());
+        let tx = AliasBuilder::new(&pk)
+            .alias("rhino")
+            .chain_id(TESTNET)
+            .timestamp(1536000000000)
+            .build()
+            .unwrap();
+        assert_eq!(tx.chain_id, TESTNET);
+        assert_eq!(tx.version, Version::V3 as u8);
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt;
 
 /// Structure that sets key and value of account data storage entry.
@@ -13,13 +14,34 @@ use std::fmt;
 /// * integral
 /// * array of bytes
 ///
+/// An entry without a value deletes the key from the storage. Deleting is only supported by
+/// Data transactions of protobuf versions, and the node returns deleted keys as such entries.
+///
 /// The size of an account data storage is unlimited.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum DataEntry<'a> {
-    Integer(&'a str, u64),
-    Boolean(&'a str, bool),
-    Binary(&'a str, &'a Vec<u8>),
-    String(&'a str, &'a str),
+    Integer(Cow<'a, str>, u64),
+    Boolean(Cow<'a, str>, bool),
+    Binary(Cow<'a, str>, Cow<'a, [u8]>),
+    String(Cow<'a, str>, Cow<'a, str>),
+    Delete(Cow<'a, str>),
+}
+
+impl<'a> DataEntry<'a> {
+    /// Converts the entry to one that owns its key and value
+    pub fn into_owned(self) -> DataEntry<'static> {
+        match self {
+            DataEntry::Integer(key, value) => DataEntry::Integer(key.into_owned().into(), value),
+            DataEntry::Boolean(key, value) => DataEntry::Boolean(key.into_owned().into(), value),
+            DataEntry::Binary(key, value) => {
+                DataEntry::Binary(key.into_owned().into(), value.into_owned().into())
+            }
+            DataEntry::String(key, value) => {
+                DataEntry::String(key.into_owned().into(), value.into_owned().into())
+            }
+            DataEntry::Delete(key) => DataEntry::Delete(key.into_owned().into()),
+        }
+    }
 }
 
 impl<'a> fmt::Display for DataEntry<'a> {
@@ -29,6 +51,7 @@ impl<'a> fmt::Display for DataEntry<'a> {
             DataEntry::Boolean(key, value) => write!(f, "Data<Bool>({}: {})", key, value),
             DataEntry::Binary(key, value) => write!(f, "Data<Binary>({}: {:?})", key, value),
             DataEntry::String(key, value) => write!(f, "Data<String>({}: {})", key, value),
+            DataEntry::Delete(key) => write!(f, "Data<Delete>({})", key),
         }
     }
 }
@@ -39,17 +62,28 @@ mod tests {
 
     #[test]
     fn test_data_entry() {
-        let data_entry = DataEntry::Integer("key1", 42);
+        let data_entry = DataEntry::Integer("key1".into(), 42);
         assert_eq!(data_entry.to_string(), "Data<Int>(key1: 42)");
 
-        let data_entry = DataEntry::Boolean("key2", true);
+        let data_entry = DataEntry::Boolean("key2".into(), true);
         assert_eq!(data_entry.to_string(), "Data<Bool>(key2: true)");
 
         let binary = vec![0u8, 1u8, 2u8];
-        let data_entry = DataEntry::Binary("key3", &binary);
+        let data_entry = DataEntry::Binary("key3".into(), (&binary).into());
         assert_eq!(data_entry.to_string(), "Data<Binary>(key3: [0, 1, 2])");
 
-        let data_entry = DataEntry::String("key4", "test");
+        let data_entry = DataEntry::String("key4".into(), "test".into());
         assert_eq!(data_entry.to_string(), "Data<String>(key4: test)");
+
+        let key = String::from("key5");
+        let data_entry = DataEntry::Binary(key.as_str().into(), (&binary).into()).into_owned();
+        drop(key);
+        assert_eq!(
+            data_entry,
+            DataEntry::Binary("key5".into(), vec![0, 1, 2].into())
+        );
+
+        let data_entry = DataEntry::Delete("key6".into());
+        assert_eq!(data_entry.to_string(), "Data<Delete>(key6)");
     }
 }
@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+
+/// Argument of a dApp function call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Arg<'a> {
+    Integer(i64),
+    Binary(Cow<'a, [u8]>),
+    String(Cow<'a, str>),
+    Boolean(bool),
+    List(Vec<Arg<'a>>),
+}
+
+impl<'a> Arg<'a> {
+    /// Converts the argument to one that owns its values
+    pub fn into_owned(self) -> Arg<'static> {
+        match self {
+            Arg::Integer(value) => Arg::Integer(value),
+            Arg::Binary(value) => Arg::Binary(value.into_owned().into()),
+            Arg::String(value) => Arg::String(value.into_owned().into()),
+            Arg::Boolean(value) => Arg::Boolean(value),
+            Arg::List(values) => Arg::List(values.into_iter().map(Arg::into_owned).collect()),
+        }
+    }
+}
+
+/// Call of a dApp callable function by an Invoke Script transaction
+///
+/// # Usage
+/// ```
+/// use wavesplatform::transaction::{Arg, FunctionCall};
+/// let call = FunctionCall::new(
+///     "deposit",
+///     vec![Arg::Integer(100), Arg::String("note".into())],
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionCall<'a> {
+    pub function: Cow<'a, str>,
+    pub args: Vec<Arg<'a>>,
+}
+
+impl<'a> FunctionCall<'a> {
+    pub fn new(function: &'a str, args: Vec<Arg<'a>>) -> FunctionCall<'a> {
+        FunctionCall {
+            function: function.into(),
+            args,
+        }
+    }
+
+    /// Converts the call to one that owns its function name and arguments
+    pub fn into_owned(self) -> FunctionCall<'static> {
+        FunctionCall {
+            function: self.function.into_owned().into(),
+            args: self.args.into_iter().map(Arg::into_owned).collect(),
+        }
+    }
+}
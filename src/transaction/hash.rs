@@ -1,4 +1,6 @@
-use base58::{FromBase58, ToBase58};
+use crate::error::{from_base58, Error};
+
+use base58::ToBase58;
 use std::fmt;
 
 /// The Transaction ID is a [`struct@Hash`]
@@ -29,10 +31,8 @@ impl Hash {
     }
 
     /// Create an [`struct@Hash`] from the base58 string.
-    pub fn from_string(base58: &str) -> Asset {
-        let mut bytes = [0u8; HASH_LENGTH];
-        bytes.copy_from_slice(base58.from_base58().unwrap().as_slice()); ////map unwrap, handle bad length
-        Hash(bytes)
+    pub fn from_string(base58: &str) -> Result<Hash, Error> {
+        Ok(Hash(from_base58(base58)?))
     }
 }
 
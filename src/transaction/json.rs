@@ -0,0 +1,1617 @@
+use crate::account::{Address, PublicKeyAccount, MAINNET};
+use crate::transaction::{
+    has_signature_version, is_protobuf_version, Arg, Asset, DataEntry, FunctionCall, Hash, Order,
+    OrderType, PriceMode, ProvenOrder, ProvenTransaction, Recipient, Transaction,
+    TransactionData::*, Type, Version, HASH_LENGTH,
+};
+
+use base58::{FromBase58, ToBase58};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ed25519_dalek::{PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{json, Map, Value};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+/// Prefix of base64-encoded binary values (scripts, binary data entries) in the node JSON
+const BASE64_PREFIX: &str = "base64:";
+
+type JsonObject = Map<String, Value>;
+
+fn to_base64(bytes: &[u8]) -> String {
+    format!("{}{}", BASE64_PREFIX, STANDARD.encode(bytes))
+}
+
+fn from_base64(name: &str, value: &str) -> Result<Vec<u8>, String> {
+    value
+        .strip_prefix(BASE64_PREFIX)
+        .and_then(|s| STANDARD.decode(s).ok())
+        .ok_or_else(|| format!("invalid base64 value of field `{}`", name))
+}
+
+fn from_base58(name: &str, value: &str, len: usize) -> Result<Vec<u8>, String> {
+    match value.from_base58() {
+        Ok(bytes) if bytes.len() == len => Ok(bytes),
+        _ => Err(format!("invalid base58 value of field `{}`", name)),
+    }
+}
+
+fn field<'v>(json: &'v JsonObject, name: &str) -> Result<&'v Value, String> {
+    json.get(name)
+        .ok_or_else(|| format!("missing field `{}`", name))
+}
+
+fn u64_field(json: &JsonObject, name: &str) -> Result<u64, String> {
+    field(json, name)?
+        .as_u64()
+        .ok_or_else(|| format!("field `{}` is not an unsigned integer", name))
+}
+
+/// Reads a signed or unsigned integer, keeping negative integers as their two's complement like
+/// the protobuf encoding does
+fn integer_field(json: &JsonObject, name: &str) -> Result<u64, String> {
+    let value = field(json, name)?;
+    value
+        .as_u64()
+        .or_else(|| value.as_i64().map(|value| value as u64))
+        .ok_or_else(|| format!("field `{}` is not an integer", name))
+}
+
+fn u8_field(json: &JsonObject, name: &str) -> Result<u8, String> {
+    u8::try_from(u64_field(json, name)?).map_err(|_| format!("field `{}` is out of range", name))
+}
+
+fn bool_field(json: &JsonObject, name: &str) -> Result<bool, String> {
+    field(json, name)?
+        .as_bool()
+        .ok_or_else(|| format!("field `{}` is not a boolean", name))
+}
+
+fn str_field<'v>(json: &'v JsonObject, name: &str) -> Result<&'v str, String> {
+    field(json, name)?
+        .as_str()
+        .ok_or_else(|| format!("field `{}` is not a string", name))
+}
+
+/// Reads a string field which may be absent or `null`
+fn str_opt_field<'v>(json: &'v JsonObject, name: &str) -> Result<Option<&'v str>, String> {
+    match json.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s)),
+        Some(_) => Err(format!("field `{}` is not a string", name)),
+    }
+}
+
+fn hash_field(json: &JsonObject, name: &str) -> Result<Hash, String> {
+    let mut bytes = [0u8; HASH_LENGTH];
+    bytes.copy_from_slice(&from_base58(name, str_field(json, name)?, HASH_LENGTH)?);
+    Ok(Hash::new(bytes))
+}
+
+/// Reads an asset ID, where `null` or `"WAVES"` stand for WAVES
+fn asset_opt_field(json: &JsonObject, name: &str) -> Result<Option<Asset>, String> {
+    match str_opt_field(json, name)? {
+        None | Some("WAVES") => Ok(None),
+        Some(_) => Ok(Some(hash_field(json, name)?)),
+    }
+}
+
+fn public_key_field(json: &JsonObject, name: &str) -> Result<PublicKeyAccount, String> {
+    let mut bytes = [0u8; PUBLIC_KEY_LENGTH];
+    bytes.copy_from_slice(&from_base58(
+        name,
+        str_field(json, name)?,
+        PUBLIC_KEY_LENGTH,
+    )?);
+    Ok(PublicKeyAccount(bytes))
+}
+
+fn address_field(json: &JsonObject, name: &str) -> Result<Address, String> {
+    Address::from_string(str_field(json, name)?)
+        .map_err(|err| format!("invalid field `{}`: {}", name, err))
+}
+
+/// Reads a recipient: an address, or an alias in the `alias:<chain ID>:<alias>` form
+fn recipient_field(json: &JsonObject, name: &str) -> Result<Recipient, String> {
+    Recipient::from_string(str_field(json, name)?)
+        .map_err(|err| format!("invalid field `{}`: {}", name, err))
+}
+
+fn script_field(json: &JsonObject, name: &str) -> Result<Option<Cow<'static, [u8]>>, String> {
+    match str_opt_field(json, name)? {
+        Some(script) => Ok(Some(from_base64(name, script)?.into())),
+        None => Ok(None),
+    }
+}
+
+/// Reads a base58-encoded attachment, where an empty string means no attachment
+fn attachment_field(json: &JsonObject, name: &str) -> Result<Option<Cow<'static, [u8]>>, String> {
+    match str_opt_field(json, name)? {
+        None | Some("") => Ok(None),
+        Some(attachment) => attachment
+            .from_base58()
+            .map(|bytes| Some(bytes.into()))
+            .map_err(|_| format!("invalid base58 value of field `{}`", name)),
+    }
+}
+
+/// Reads the chain ID from the sender address, or returns [`MAINNET`] if there is none
+fn sender_chain_id(json: &JsonObject) -> Result<u8, String> {
+    match str_opt_field(json, "sender")? {
+        Some(_) => Ok(address_field(json, "sender")?.chain_id()),
+        None => Ok(MAINNET),
+    }
+}
+
+fn proofs_json(proofs: &[Vec<u8>]) -> Value {
+    json!(proofs
+        .iter()
+        .map(|p| p.to_base58())
+        .collect::<Vec<String>>())
+}
+
+fn proofs_field(json: &JsonObject, name: &str) -> Result<Vec<Vec<u8>>, String> {
+    field(json, name)?
+        .as_array()
+        .and_then(|proofs| {
+            proofs
+                .iter()
+                .map(|p| p.as_str().and_then(|s| s.from_base58().ok()))
+                .collect::<Option<Vec<Vec<u8>>>>()
+        })
+        .ok_or_else(|| format!("invalid value of field `{}`", name))
+}
+
+fn data_entry_json(entry: &DataEntry) -> Value {
+    match entry {
+        DataEntry::Integer(key, value) => json!({"key": key, "type": "integer", "value": value}),
+        DataEntry::Boolean(key, value) => json!({"key": key, "type": "boolean", "value": value}),
+        DataEntry::Binary(key, value) => {
+            json!({"key": key, "type": "binary", "value": to_base64(value)})
+        }
+        DataEntry::String(key, value) => json!({"key": key, "type": "string", "value": value}),
+        DataEntry::Delete(key) => json!({"key": key, "value": null}),
+    }
+}
+
+fn arg_json(arg: &Arg) -> Value {
+    match arg {
+        Arg::Integer(value) => json!({"type": "integer", "value": value}),
+        Arg::Binary(value) => json!({"type": "binary", "value": to_base64(value)}),
+        Arg::String(value) => json!({"type": "string", "value": value}),
+        Arg::Boolean(value) => json!({"type": "boolean", "value": value}),
+        Arg::List(values) => {
+            json!({"type": "list", "value": values.iter().map(arg_json).collect::<Vec<Value>>()})
+        }
+    }
+}
+
+fn arg_from_json(value: &Value) -> Result<Arg<'static>, String> {
+    let json = value
+        .as_object()
+        .ok_or_else(|| "function argument is not an object".to_string())?;
+    match str_field(json, "type")? {
+        "integer" => field(json, "value")?
+            .as_i64()
+            .map(Arg::Integer)
+            .ok_or_else(|| "field `value` is not an integer".to_string()),
+        "binary" => Ok(Arg::Binary(
+            from_base64("value", str_field(json, "value")?)?.into(),
+        )),
+        "string" => Ok(Arg::String(str_field(json, "value")?.to_string().into())),
+        "boolean" => Ok(Arg::Boolean(bool_field(json, "value")?)),
+        "list" => Ok(Arg::List(
+            field(json, "value")?
+                .as_array()
+                .ok_or_else(|| "field `value` is not an array".to_string())?
+                .iter()
+                .map(arg_from_json)
+                .collect::<Result<Vec<Arg>, String>>()?,
+        )),
+        other => Err(format!("unknown function argument type `{}`", other)),
+    }
+}
+
+fn function_call_json(call: &FunctionCall) -> Value {
+    json!({
+        "function": call.function,
+        "args": call.args.iter().map(arg_json).collect::<Vec<Value>>(),
+    })
+}
+
+fn function_call_from_json(value: &Value) -> Result<FunctionCall<'static>, String> {
+    let json = value
+        .as_object()
+        .ok_or_else(|| "field `call` is not an object".to_string())?;
+    Ok(FunctionCall {
+        function: str_field(json, "function")?.to_string().into(),
+        args: field(json, "args")?
+            .as_array()
+            .ok_or_else(|| "field `args` is not an array".to_string())?
+            .iter()
+            .map(arg_from_json)
+            .collect::<Result<Vec<Arg>, String>>()?,
+    })
+}
+
+fn data_entry_from_json(value: &Value) -> Result<DataEntry<'static>, String> {
+    let json = value
+        .as_object()
+        .ok_or_else(|| "data entry is not an object".to_string())?;
+    let key = Cow::from(str_field(json, "key")?.to_string());
+    if json.get("value").is_none_or(Value::is_null) {
+        return Ok(DataEntry::Delete(key));
+    }
+    match str_field(json, "type")? {
+        "integer" => Ok(DataEntry::Integer(key, integer_field(json, "value")?)),
+        "boolean" => Ok(DataEntry::Boolean(key, bool_field(json, "value")?)),
+        "binary" => Ok(DataEntry::Binary(
+            key,
+            from_base64("value", str_field(json, "value")?)?.into(),
+        )),
+        "string" => Ok(DataEntry::String(
+            key,
+            str_field(json, "value")?.to_string().into(),
+        )),
+        other => Err(format!("unknown data entry type `{}`", other)),
+    }
+}
+
+fn order_json(proven: &ProvenOrder) -> Value {
+    let order = &proven.order;
+    let mut json = json!({
+        "version": order.version,
+        "id": order.id().to_string(),
+        "senderPublicKey": order.sender_public_key.to_string(),
+        "matcherPublicKey": order.matcher_public_key.to_string(),
+        "assetPair": {
+            "amountAsset": order.amount_asset.map(|a| a.to_string()),
+            "priceAsset": order.price_asset.map(|a| a.to_string()),
+        },
+        "orderType": order.order_type.to_string(),
+        "amount": order.amount,
+        "price": order.price,
+        "timestamp": order.timestamp,
+        "expiration": order.expiration,
+        "matcherFee": order.matcher_fee,
+        "proofs": proofs_json(&proven.proofs),
+    });
+    if let Some(signature) = proven.proofs.first() {
+        json["signature"] = json!(signature.to_base58());
+    }
+    if order.version >= Version::V3 as u8 {
+        json["matcherFeeAssetId"] = json!(order.matcher_fee_asset.map(|a| a.to_string()));
+    }
+    if order.version >= Version::V4 as u8 {
+        json["eip712Signature"] = Value::Null;
+        json["priceMode"] = json!(order.price_mode.to_string());
+    }
+    json
+}
+
+/// Reads an order of an Exchange transaction, which has the chain ID of the transaction unless
+/// the order states its own
+fn order_from_json(value: &Value, name: &str, chain_id: u8) -> Result<ProvenOrder, String> {
+    let json = value
+        .as_object()
+        .ok_or_else(|| format!("field `{}` is not an object", name))?;
+    let version = u8_field(json, "version")?;
+    if !(Version::V1 as u8..=Version::V4 as u8).contains(&version) {
+        return Err(format!("unsupported order version {}", version));
+    }
+    let asset_pair = field(json, "assetPair")?
+        .as_object()
+        .ok_or_else(|| "field `assetPair` is not an object".to_string())?;
+    let order_type = match str_field(json, "orderType")? {
+        "buy" => OrderType::Buy,
+        "sell" => OrderType::Sell,
+        other => return Err(format!("unknown order type `{}`", other)),
+    };
+    let price_mode = match str_opt_field(json, "priceMode")? {
+        None | Some("default") => PriceMode::Default,
+        Some("fixedDecimals") => PriceMode::FixedDecimals,
+        Some("assetDecimals") => PriceMode::AssetDecimals,
+        Some(other) => return Err(format!("unknown price mode `{}`", other)),
+    };
+    let matcher_fee_asset = asset_opt_field(json, "matcherFeeAssetId")?;
+    if matcher_fee_asset.is_some() && version < Version::V3 as u8 {
+        return Err(format!(
+            "field `matcherFeeAssetId` must be null for order version {}",
+            version
+        ));
+    }
+    let proofs = if json.contains_key("proofs") {
+        proofs_field(json, "proofs")?
+    } else {
+        vec![from_base58(
+            "signature",
+            str_field(json, "signature")?,
+            SIGNATURE_LENGTH,
+        )?]
+    };
+    let order = Order {
+        version,
+        chain_id: match json.get("chainId") {
+            Some(_) => u8_field(json, "chainId")?,
+            None => chain_id,
+        },
+        sender_public_key: public_key_field(json, "senderPublicKey")?,
+        matcher_public_key: public_key_field(json, "matcherPublicKey")?,
+        amount_asset: asset_opt_field(asset_pair, "amountAsset")?,
+        price_asset: asset_opt_field(asset_pair, "priceAsset")?,
+        order_type,
+        price_mode,
+        amount: u64_field(json, "amount")?,
+        price: u64_field(json, "price")?,
+        timestamp: u64_field(json, "timestamp")?,
+        expiration: u64_field(json, "expiration")?,
+        matcher_fee: u64_field(json, "matcherFee")?,
+        matcher_fee_asset,
+    };
+    Ok(order.with_proofs(proofs))
+}
+
+/// Returns `true` if the chain ID is part of the signed transaction bytes on its own, not only as
+/// part of the recipient addresses
+fn signs_chain_id(tx_type: Type, protobuf: bool) -> bool {
+    protobuf
+        || matches!(
+            tx_type,
+            Type::Issue
+                | Type::Reissue
+                | Type::Burn
+                | Type::LeaseCancel
+                | Type::Alias
+                | Type::SetScript
+                | Type::SetAssetScript
+        )
+}
+
+impl<'a> Transaction<'a> {
+    /// Returns the transaction as a JSON object in the format of the node REST API
+    fn to_json(&self) -> JsonObject {
+        let mut json = Map::new();
+        json.insert("type".into(), json!(self.type_id));
+        json.insert("version".into(), json!(self.version));
+        json.insert("id".into(), json!(self.id().to_string()));
+        json.insert(
+            "senderPublicKey".into(),
+            json!(self.sender_public_key.to_string()),
+        );
+        json.insert("fee".into(), json!(self.fee));
+        json.insert("feeAssetId".into(), Value::Null);
+        json.insert("timestamp".into(), json!(self.timestamp));
+        // like the node, only legacy Transfer, Exchange, Lease, Mass Transfer, Data, Sponsor and
+        // Invoke Script omit the chain ID
+        if self.is_protobuf()
+            || !matches!(
+                self.data,
+                Transfer { .. }
+                    | Exchange { .. }
+                    | Lease { .. }
+                    | MassTransfer { .. }
+                    | Data { .. }
+                    | Sponsor { .. }
+                    | InvokeScript { .. }
+            )
+        {
+            json.insert("chainId".into(), json!(self.chain_id));
+        }
+
+        let script_json = |script: &Option<Cow<[u8]>>| match script {
+            Some(bytes) => json!(to_base64(bytes)),
+            None => Value::Null,
+        };
+        let attachment_json = |attachment: &Option<Cow<[u8]>>| {
+            json!(attachment
+                .as_ref()
+                .map(|bytes| bytes.to_base58())
+                .unwrap_or_default())
+        };
+
+        let fields = match &self.data {
+            Issue {
+                name,
+                description,
+                quantity,
+                decimals,
+                reissuable,
+                script,
+            } => json!({
+                "name": name,
+                "description": description,
+                "quantity": quantity,
+                "decimals": decimals,
+                "reissuable": reissuable,
+                "script": script_json(script),
+            }),
+            Transfer {
+                recipient,
+                asset,
+                amount,
+                fee_asset,
+                attachment,
+            } => json!({
+                "recipient": recipient.to_string(),
+                "assetId": asset.map(|a| a.to_string()),
+                "amount": amount,
+                "feeAssetId": fee_asset.map(|a| a.to_string()),
+                "attachment": attachment_json(attachment),
+            }),
+            Reissue {
+                asset,
+                quantity,
+                reissuable,
+            } => json!({
+                "assetId": asset.to_string(),
+                "quantity": quantity,
+                "reissuable": reissuable,
+            }),
+            Burn { asset, quantity } => json!({
+                "assetId": asset.to_string(),
+                "amount": quantity,
+            }),
+            Exchange {
+                order1,
+                order2,
+                amount,
+                price,
+                buy_matcher_fee,
+                sell_matcher_fee,
+            } => json!({
+                "order1": order_json(order1),
+                "order2": order_json(order2),
+                "amount": amount,
+                "price": price,
+                "buyMatcherFee": buy_matcher_fee,
+                "sellMatcherFee": sell_matcher_fee,
+            }),
+            Lease { recipient, amount } => json!({
+                "recipient": recipient.to_string(),
+                "amount": amount,
+            }),
+            CancelLease { lease_id } => json!({
+                "leaseId": lease_id.to_string(),
+            }),
+            Alias { alias } => json!({
+                "alias": alias,
+            }),
+            MassTransfer {
+                asset,
+                transfers,
+                attachment,
+            } => json!({
+                "assetId": asset.map(|a| a.to_string()),
+                "transfers": transfers
+                    .iter()
+                    .map(|(recipient, amount)| {
+                        json!({"recipient": recipient.to_string(), "amount": amount})
+                    })
+                    .collect::<Vec<Value>>(),
+                "attachment": attachment_json(attachment),
+            }),
+            Data { data } => json!({
+                "data": data.iter().map(data_entry_json).collect::<Vec<Value>>(),
+            }),
+            SetScript { script } => json!({
+                "script": script_json(script),
+            }),
+            Sponsor { asset, rate } => json!({
+                "assetId": asset.to_string(),
+                "minSponsoredAssetFee": rate,
+            }),
+            SetAssetScript { asset, script } => json!({
+                "assetId": asset.to_string(),
+                "script": script_json(script),
+            }),
+            InvokeScript {
+                dapp,
+                call,
+                payments,
+                fee_asset,
+            } => json!({
+                "dApp": dapp.to_string(),
+                "call": call.as_ref().map(function_call_json),
+                "payment": payments
+                    .iter()
+                    .map(|(asset, amount)| {
+                        json!({"amount": amount, "assetId": asset.map(|a| a.to_string())})
+                    })
+                    .collect::<Vec<Value>>(),
+                "feeAssetId": fee_asset.map(|a| a.to_string()),
+            }),
+            UpdateAssetInfo {
+                asset,
+                name,
+                description,
+            } => json!({
+                "assetId": asset.to_string(),
+                "name": name,
+                "description": description,
+            }),
+        };
+        if let Value::Object(fields) = fields {
+            json.extend(fields);
+        }
+        json
+    }
+}
+
+impl Transaction<'static> {
+    /// Reads a transaction from a JSON object in the format of the node REST API
+    fn from_json(json: &JsonObject) -> Result<Transaction<'static>, String> {
+        let type_id = u8_field(json, "type")?;
+        let tx_type =
+            Type::try_from(type_id).map_err(|_| format!("unknown transaction type {}", type_id))?;
+        let version = u8_field(json, "version")?;
+        if version < Version::V1 as u8 {
+            return Err(format!(
+                "unsupported version {} of transaction type {}",
+                version, type_id
+            ));
+        }
+
+        let data = match tx_type {
+            Type::Issue => Issue {
+                name: str_field(json, "name")?.to_string().into(),
+                description: str_field(json, "description")?.to_string().into(),
+                quantity: u64_field(json, "quantity")?,
+                decimals: u8_field(json, "decimals")?,
+                reissuable: bool_field(json, "reissuable")?,
+                script: script_field(json, "script")?,
+            },
+            Type::Transfer => Transfer {
+                recipient: recipient_field(json, "recipient")?,
+                asset: asset_opt_field(json, "assetId")?,
+                amount: u64_field(json, "amount")?,
+                fee_asset: asset_opt_field(json, "feeAssetId")?,
+                attachment: attachment_field(json, "attachment")?,
+            },
+            Type::Reissue => Reissue {
+                asset: hash_field(json, "assetId")?,
+                quantity: u64_field(json, "quantity")?,
+                reissuable: bool_field(json, "reissuable")?,
+            },
+            Type::Burn => Burn {
+                asset: hash_field(json, "assetId")?,
+                quantity: u64_field(json, "amount")?,
+            },
+            Type::Exchange => {
+                let chain_id = match json.get("chainId") {
+                    Some(_) => u8_field(json, "chainId")?,
+                    None => sender_chain_id(json)?,
+                };
+                Exchange {
+                    order1: order_from_json(field(json, "order1")?, "order1", chain_id)?,
+                    order2: order_from_json(field(json, "order2")?, "order2", chain_id)?,
+                    amount: u64_field(json, "amount")?,
+                    price: u64_field(json, "price")?,
+                    buy_matcher_fee: u64_field(json, "buyMatcherFee")?,
+                    sell_matcher_fee: u64_field(json, "sellMatcherFee")?,
+                }
+            }
+            Type::Lease => Lease {
+                recipient: recipient_field(json, "recipient")?,
+                amount: u64_field(json, "amount")?,
+            },
+            Type::LeaseCancel => CancelLease {
+                lease_id: hash_field(json, "leaseId")?,
+            },
+            Type::Alias => Alias {
+                alias: str_field(json, "alias")?.to_string().into(),
+            },
+            Type::MassTransfer => {
+                let transfers = field(json, "transfers")?
+                    .as_array()
+                    .ok_or_else(|| "field `transfers` is not an array".to_string())?
+                    .iter()
+                    .map(|transfer| {
+                        let transfer = transfer
+                            .as_object()
+                            .ok_or_else(|| "transfer is not an object".to_string())?;
+                        Ok((
+                            recipient_field(transfer, "recipient")?,
+                            u64_field(transfer, "amount")?,
+                        ))
+                    })
+                    .collect::<Result<Vec<(Recipient, u64)>, String>>()?;
+                MassTransfer {
+                    asset: asset_opt_field(json, "assetId")?,
+                    transfers,
+                    attachment: attachment_field(json, "attachment")?,
+                }
+            }
+            Type::Data => Data {
+                data: field(json, "data")?
+                    .as_array()
+                    .ok_or_else(|| "field `data` is not an array".to_string())?
+                    .iter()
+                    .map(data_entry_from_json)
+                    .collect::<Result<Vec<DataEntry>, String>>()?,
+            },
+            Type::SetScript => SetScript {
+                script: script_field(json, "script")?,
+            },
+            Type::Sponsor => Sponsor {
+                asset: hash_field(json, "assetId")?,
+                rate: match json.get("minSponsoredAssetFee") {
+                    None | Some(Value::Null) => None,
+                    Some(_) => Some(u64_field(json, "minSponsoredAssetFee")?),
+                },
+            },
+            Type::SetAssetScript => SetAssetScript {
+                asset: hash_field(json, "assetId")?,
+                script: script_field(json, "script")?,
+            },
+            Type::InvokeScript => {
+                let payments = field(json, "payment")?
+                    .as_array()
+                    .ok_or_else(|| "field `payment` is not an array".to_string())?
+                    .iter()
+                    .map(|payment| {
+                        let payment = payment
+                            .as_object()
+                            .ok_or_else(|| "payment is not an object".to_string())?;
+                        Ok((
+                            asset_opt_field(payment, "assetId")?,
+                            u64_field(payment, "amount")?,
+                        ))
+                    })
+                    .collect::<Result<Vec<(Option<Asset>, u64)>, String>>()?;
+                InvokeScript {
+                    dapp: recipient_field(json, "dApp")?,
+                    call: match json.get("call") {
+                        None | Some(Value::Null) => None,
+                        Some(call) => Some(function_call_from_json(call)?),
+                    },
+                    payments,
+                    fee_asset: asset_opt_field(json, "feeAssetId")?,
+                }
+            }
+            Type::UpdateAssetInfo => UpdateAssetInfo {
+                asset: hash_field(json, "assetId")?,
+                name: str_field(json, "name")?.to_string().into(),
+                description: str_field(json, "description")?.to_string().into(),
+            },
+        };
+        if !matches!(data, Transfer { .. } | InvokeScript { .. })
+            && asset_opt_field(json, "feeAssetId")?.is_some()
+        {
+            return Err("field `feeAssetId` must be null for this transaction type".to_string());
+        }
+
+        let protobuf = is_protobuf_version(tx_type, version);
+        // the node returns a null chain ID for version 1 transactions, which do not sign it
+        let signs_chain_id = signs_chain_id(tx_type, protobuf)
+            && !(version == Version::V1 as u8 && has_signature_version(tx_type));
+        let has_chain_id = json.get("chainId").is_some_and(|v| !v.is_null());
+        let chain_id = if has_chain_id || signs_chain_id {
+            u8_field(json, "chainId")?
+        } else {
+            match &data {
+                Transfer { recipient, .. } | Lease { recipient, .. } => recipient.chain_id(),
+                InvokeScript { dapp, .. } => dapp.chain_id(),
+                MassTransfer { transfers, .. } if !transfers.is_empty() => {
+                    transfers[0].0.chain_id()
+                }
+                _ => sender_chain_id(json)?,
+            }
+        };
+
+        Ok(Transaction {
+            data,
+            fee: u64_field(json, "fee")?,
+            timestamp: u64_field(json, "timestamp")?,
+            sender_public_key: public_key_field(json, "senderPublicKey")?,
+            chain_id,
+            type_id,
+            version,
+        })
+    }
+}
+
+impl<'a> Serialize for Transaction<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Transaction<'static> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = JsonObject::deserialize(deserializer)?;
+        Transaction::from_json(&json).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Hash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Hash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let base58 = String::deserialize(deserializer)?;
+        let bytes = from_base58("hash", &base58, HASH_LENGTH).map_err(D::Error::custom)?;
+        let mut hash = [0u8; HASH_LENGTH];
+        hash.copy_from_slice(&bytes);
+        Ok(Hash::new(hash))
+    }
+}
+
+impl<'a> Serialize for DataEntry<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        data_entry_json(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DataEntry<'static> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        data_entry_from_json(&Value::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl<'a> Serialize for ProvenTransaction<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut json = self.tx.to_json();
+        json.insert("proofs".into(), proofs_json(&self.proofs));
+        if let (true, Some(signature)) = (self.tx.is_signature_version(), self.proofs.first()) {
+            json.insert("signature".into(), json!(signature.to_base58()));
+        }
+        json.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ProvenTransaction<'static> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = JsonObject::deserialize(deserializer)?;
+        let tx = Transaction::from_json(&json).map_err(D::Error::custom)?;
+        // older nodes return only the signature of version 1 transactions
+        let proofs = match (json.get("proofs"), json.get("signature")) {
+            (None, Some(_)) => str_field(&json, "signature")
+                .and_then(|signature| from_base58("signature", signature, SIGNATURE_LENGTH))
+                .map(|signature| vec![signature]),
+            _ => proofs_field(&json, "proofs"),
+        }
+        .map_err(D::Error::custom)?;
+        Ok(ProvenTransaction { tx, proofs })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::TESTNET;
+    use crate::util::sig_verify;
+
+    use ed25519_dalek::SIGNATURE_LENGTH;
+
+    /// Signed transactions of every type but Exchange, Invoke Script and Update Asset Info, which
+    /// have their own tests, as returned by Testnet nodes
+    const FIXTURES: [&str; 14] = [
+        r#"{
+            "type": 3,
+            "id": "3kuZKAeyjcqavmezy86sWCAeXrgt3HBKa4HA8CZdT8nH",
+            "fee": 100000,
+            "feeAssetId": null,
+            "timestamp": 1662934147902,
+            "version": 3,
+            "chainId": 84,
+            "sender": "3MtdtMUPMHG8FfQDG9mJwdbky4yCNA1JXBc",
+            "senderPublicKey": "7BN97QoywUXEdezfLFD7nBrdHvHVhYQDqPPSmHD4x3am",
+            "proofs": [
+                "5LfAzHme4wSmyqi8SKdJ5y6Jb4TbMuHzL6YLs7UjrMZAXR8Hxdhnx9AmDf6YCC6CrtaySxVRYFnEFSegn3fcxot1"
+            ],
+            "assetId": "3kuZKAeyjcqavmezy86sWCAeXrgt3HBKa4HA8CZdT8nH",
+            "name": "wunderbon",
+            "quantity": 1,
+            "reissuable": false,
+            "decimals": 0,
+            "description": "d002e901615c0c44c16bdcb1237f0590a726484c5760a9614073e9489ba5fe22c08ef4fbedb681827878b3fc945bc936",
+            "script": null,
+            "applicationStatus": "succeeded"
+        }"#,
+        r#"{
+            "type": 4,
+            "id": "9FYGngVs7GyiV98vmd7wBKpLrqHNZJycVBavpnbL13Ph",
+            "fee": 100000,
+            "feeAssetId": null,
+            "timestamp": 1662471467319,
+            "version": 2,
+            "sender": "3Myqjf1D44wR8Vko4Tr5CwSzRNo2Vg9S7u7",
+            "senderPublicKey": "9oYuF7V66UNpD2AgYHb6t2j9GYrf3c6hRvwtop6uD6Rx",
+            "proofs": [
+                "42WLGw3i3ALcP6cAEYBFwwbaQSiB68kGeAHHHosA5pUaRFNCH3UCTAHPbRMtHfLsweRFxND565fNuGb6cjJRKfD4"
+            ],
+            "recipient": "3Mq3pueXcAgLcuWvJzJ4ndRHfqYgjUZvL7q",
+            "assetId": null,
+            "feeAsset": null,
+            "amount": 200000000,
+            "attachment": "",
+            "height": 2217333,
+            "applicationStatus": "succeeded",
+            "spentComplexity": 0
+        }"#,
+        r#"{
+            "type": 5,
+            "id": "Vj7jNgVuomr3EFmcw9fhkDX3ytzhqaGZEZ7bqDMBXkC",
+            "fee": 100000000,
+            "feeAssetId": null,
+            "timestamp": 1662560629705,
+            "version": 3,
+            "chainId": 84,
+            "sender": "3MxtrLkrbcG28uTvmbKmhrwGrR65ooHVYvK",
+            "senderPublicKey": "CJJu3U5UL35Dhq5KGRZw2rdundAv2pPgB7GF21G3y4vt",
+            "proofs": [
+                "2Jm7NXfPrnEzjUFNJT18KXzPLd5JsSBZkhhDKxVAyzT7dekoQMPbewtoaspMHVCvPckxpwSqhiHZwGe7vJiPiKwb"
+            ],
+            "assetId": "8bt2MZjuUCJPmfucPfaZPTXqrxmoCHCC8gVnbjZ7bhH6",
+            "quantity": 12,
+            "reissuable": true,
+            "height": 2218816,
+            "applicationStatus": "succeeded",
+            "spentComplexity": 0
+        }"#,
+        r#"{
+            "type": 5,
+            "id": "44seokQaBquAwDweKC4mbmHvmu2heWrUhKNGUakwZxRf",
+            "fee": 100000,
+            "feeAssetId": null,
+            "timestamp": 1662827949964,
+            "version": 2,
+            "sender": "3MshKh4yJizTuzUEMxN5v7GBYoHWvu9vWaL",
+            "senderPublicKey": "4npPDnaTRo6UMvfcbNqxL7ibDQVLf1mSmnK5nhwuZoA2",
+            "proofs": [
+                "2MfH4At8SH34XHFxKnm1BvDLJKqsRLNr7dFmbGjS3pZo3qqgsiseKkbjQc1Q3dbrqqqPPCoJRYcjnnUdwdFSn6iw"
+            ],
+            "assetId": "F2nUWJXu3DC6vGTUBLpZC76EpXSWYrk5ZcMmtzvGWgeA",
+            "quantity": 1000000000000000,
+            "reissuable": true,
+            "chainId": 84,
+            "applicationStatus": "succeeded"
+        }"#,
+        r#"{
+            "type": 6,
+            "id": "7Ruo9tnYTuBKTRwbSfG2TLooP4v6pz8SkTx1hvCgfJLU",
+            "fee": 100000,
+            "feeAssetId": null,
+            "timestamp": 1662563002549,
+            "version": 3,
+            "chainId": 84,
+            "sender": "3MxtrLkrbcG28uTvmbKmhrwGrR65ooHVYvK",
+            "senderPublicKey": "CJJu3U5UL35Dhq5KGRZw2rdundAv2pPgB7GF21G3y4vt",
+            "proofs": [
+                "cfXT7uuz5MusPhpiM19VywHi3m8bzXUdgNx5MBFAyHd5532NDDdvUCM4P55FzN9BggumWzy2Uz1cbng6oVDW1Ay"
+            ],
+            "assetId": "8bt2MZjuUCJPmfucPfaZPTXqrxmoCHCC8gVnbjZ7bhH6",
+            "amount": 12,
+            "height": 2218855,
+            "applicationStatus": "succeeded",
+            "spentComplexity": 0
+        }"#,
+        r#"{
+            "type": 8,
+            "id": "5EWudZk4xXaqRezrh26zqjbNeAzvEzDATjs4paKdyhGy",
+            "fee": 100000,
+            "feeAssetId": null,
+            "timestamp": 1662564976087,
+            "version": 3,
+            "chainId": 84,
+            "sender": "3Mq3pueXcAgLcuWvJzJ4ndRHfqYgjUZvL7q",
+            "senderPublicKey": "8jDzNuHZwuTTo6WvZMdSoNc8ydY6a7UnxvwHZ8kooMuS",
+            "proofs": [
+                "c6Uiq5Mb8G4zxEBo4QjTHKVQ5rcdEbYJXbVKZDpithWDsoGHFryMUFdvMLZB3ai7ZzmbvVVFniqzJuLn6yjXabR"
+            ],
+            "amount": 100,
+            "recipient": "3MxtrLkrbcG28uTvmbKmhrwGrR65ooHVYvK",
+            "height": 2218886,
+            "applicationStatus": "succeeded",
+            "spentComplexity": 0,
+            "status": "active"
+        }"#,
+        r#"{
+            "type": 8,
+            "id": "FL9juc4i2e5L2LnnrcagWQf7LYBmcJrxrxQdBrxNkwjx",
+            "fee": 100000,
+            "feeAssetId": null,
+            "timestamp": 1662829781999,
+            "version": 2,
+            "sender": "3MrmhLagifesiDUvxqsK6oW9tHEQVKzs6JJ",
+            "senderPublicKey": "JBGeneKgUJgvPVEGddZgdKfStLvHqUU5xtJs5WhrtDrD",
+            "proofs": [
+                "5JFFmM1DKYyiMW35GPasuRjYNmn4aMnE5NmqgPtmN1TZfPzAYGeDg663kT6HCzxUz5yVpgocisgroTbgPv7nJB8Q"
+            ],
+            "amount": 100000000,
+            "recipient": "3Mvr7snJsF3F6QWC9AzxuFPfaftELDHGHXh",
+            "applicationStatus": "succeeded"
+        }"#,
+        r#"{
+            "type": 9,
+            "id": "FoPVrSqzK74bwt8hgCDsEb48HJv7g2nvjeCW5wBoWpXb",
+            "fee": 100000,
+            "feeAssetId": null,
+            "timestamp": 1662567297736,
+            "version": 3,
+            "chainId": 84,
+            "sender": "3Mq3pueXcAgLcuWvJzJ4ndRHfqYgjUZvL7q",
+            "senderPublicKey": "8jDzNuHZwuTTo6WvZMdSoNc8ydY6a7UnxvwHZ8kooMuS",
+            "proofs": [
+                "24AeoHN28JoQiEj3eZCgiorhzoY67KQ1BTBbJ2dRyPmUMXF4XiEjXx8cozQSJUKSEftKuf8uAShNistFKwgwPFke"
+            ],
+            "leaseId": "5EWudZk4xXaqRezrh26zqjbNeAzvEzDATjs4paKdyhGy",
+            "height": 2218925,
+            "applicationStatus": "succeeded",
+            "spentComplexity": 0,
+            "lease": {
+                "id": "5EWudZk4xXaqRezrh26zqjbNeAzvEzDATjs4paKdyhGy",
+                "originTransactionId": "5EWudZk4xXaqRezrh26zqjbNeAzvEzDATjs4paKdyhGy",
+                "sender": "3Mq3pueXcAgLcuWvJzJ4ndRHfqYgjUZvL7q",
+                "recipient": "3MxtrLkrbcG28uTvmbKmhrwGrR65ooHVYvK",
+                "amount": 100,
+                "height": 2218886,
+                "status": "canceled",
+                "cancelHeight": 2218925,
+                "cancelTransactionId": "FoPVrSqzK74bwt8hgCDsEb48HJv7g2nvjeCW5wBoWpXb"
+            }
+        }"#,
+        r#"{
+            "type": 10,
+            "id": "5Hri2XC3QFqP4MNHa84rdyoAAQKiL8ijhVy2WmPjRwdv",
+            "fee": 100000,
+            "feeAssetId": null,
+            "timestamp": 1662650000377,
+            "version": 3,
+            "chainId": 84,
+            "sender": "3MxtrLkrbcG28uTvmbKmhrwGrR65ooHVYvK",
+            "senderPublicKey": "CJJu3U5UL35Dhq5KGRZw2rdundAv2pPgB7GF21G3y4vt",
+            "proofs": [
+                "5h3nNEdPy6h7NT3HuNqBpALbRaf39SRCsDdAj6aWy6Jy3MjCEnja1ibkcUyf7xZECtUNq5nhhz7GtRbUmxK3EBR2"
+            ],
+            "alias": "alias1662650000377",
+            "height": 2220282,
+            "applicationStatus": "succeeded",
+            "spentComplexity": 0
+        }"#,
+        r#"{
+            "type": 11,
+            "id": "JCGHq3LF2uRSTumkwmCv42kbTpG6dN62cMAsy3JywmA1",
+            "fee": 200000,
+            "feeAssetId": null,
+            "timestamp": 1662659613341,
+            "version": 3,
+            "chainId": 84,
+            "sender": "3MxtrLkrbcG28uTvmbKmhrwGrR65ooHVYvK",
+            "senderPublicKey": "CJJu3U5UL35Dhq5KGRZw2rdundAv2pPgB7GF21G3y4vt",
+            "proofs": [
+                "hYyXVmDXqosvjff6uEVn5E8wQQoDvKJkfaoMdYmxKjD3ebjQqZjnZ2pk15XTYRXDEsd1ke9EDGgMmt59PZY2a4X"
+            ],
+            "assetId": null,
+            "attachment": "Ldp",
+            "transferCount": 2,
+            "totalAmount": 22,
+            "transfers": [
+                {
+                    "recipient": "3MxtrLkrbcG28uTvmbKmhrwGrR65ooHVYvK",
+                    "amount": 10
+                },
+                {
+                    "recipient": "3MxjhrvCr1nnDxvNJiCQfSC557gd8QYEhDx",
+                    "amount": 12
+                }
+            ],
+            "height": 2220438,
+            "applicationStatus": "succeeded",
+            "spentComplexity": 0
+        }"#,
+        r#"{
+            "type": 12,
+            "id": "Aui38ZYPbNAEz8K2dvfN1bMT6FzXziqjjeSceCCizRmJ",
+            "fee": 100000,
+            "feeAssetId": null,
+            "timestamp": 1662973081593,
+            "version": 2,
+            "chainId": 84,
+            "sender": "3N4y2QYmLLS3rykgsETw2c4277zgoT7yt8s",
+            "senderPublicKey": "CvVMXsu1Q5N53E99geN9hU54C6z1oahF3LV5iedtmiKf",
+            "proofs": [
+                "4BwxM6gVWNJiS4Kcj6YznWnbkDofhToUyocQm5PDrEEVCofoiMYqXCri8jq1jYL2NDHDYhZkkXbwkaAAP6x6qukF"
+            ],
+            "data": [
+                {
+                    "key": "price_C1iWsKGqLwjHUndiQ7iXpdmPum9PeCDFfyXBdJJosDRS",
+                    "type": "integer",
+                    "value": 23921210
+                },
+                {
+                    "key": "price_4LHHvYGNKJUg5hj65aGD5vgScvCBmLpdRFtjokvCjSL8",
+                    "type": "integer",
+                    "value": 49216
+                },
+                {
+                    "key": "price_Ajso6nTTjptu2UHLx6hfSXVtHFtRBJCkKYd5SAyj7zf5",
+                    "type": "integer",
+                    "value": 2954553
+                },
+                {
+                    "key": "price_8t4DPWTwPzpatHA9AkTxWAB47THnYzBsDnoY7fQqbG91",
+                    "type": "integer",
+                    "value": 2723630
+                },
+                {
+                    "key": "price_2744_C1iWsKGqLwjHUndiQ7iXpdmPum9PeCDFfyXBdJJosDRS",
+                    "type": "integer",
+                    "value": 23921210
+                },
+                {
+                    "key": "price_2744_4LHHvYGNKJUg5hj65aGD5vgScvCBmLpdRFtjokvCjSL8",
+                    "type": "integer",
+                    "value": 49216
+                },
+                {
+                    "key": "price_2744_Ajso6nTTjptu2UHLx6hfSXVtHFtRBJCkKYd5SAyj7zf5",
+                    "type": "integer",
+                    "value": 2954553
+                },
+                {
+                    "key": "price_2744_8t4DPWTwPzpatHA9AkTxWAB47THnYzBsDnoY7fQqbG91",
+                    "type": "integer",
+                    "value": 2723630
+                }
+            ],
+            "applicationStatus": "succeeded"
+        }"#,
+        r#"{
+            "type": 13,
+            "id": "7ktiheE59NRFuruYEnf6EwkKjeRwxhy6Arq6FFE2ABCZ",
+            "fee": 100000,
+            "feeAssetId": null,
+            "timestamp": 1662703806622,
+            "version": 3,
+            "chainId": 84,
+            "sender": "3Ms6jp75u5qnfmAgWpxbt9xHv7znBp7RHnq",
+            "senderPublicKey": "ASA4fMdz5FirDREfB34PPi67QxLHMt8tvzRQDT64juiM",
+            "proofs": [
+                "26rAE26eR3UYUkXGbKNgEcTpjXUVCfu7zrWA3epPZhamdHdNoFPbYbQgp1jBYicq33R5Q5VVEenGz61H8Nt4y6dU"
+            ],
+            "script": "base64:AAIFAAAAAAAAAAsIAhIHCgUCBAEIEQAAAAAAAAABAAAAA2ludgEAAAAEY2FsbAAAAAUAAAACYnYAAAABYgAAAANpbnQAAAADc3RyAAAABGxpc3QEAAAABWFzc2V0CQAEQgAAAAUCAAAABUFzc2V0AgAAAAAAAAAAAAAAAAEAAAAAAAAAAAAGBAAAAAdhc3NldElkCQAEOAAAAAEFAAAABWFzc2V0BAAAAAVsZWFzZQkABEQAAAACCAUAAAADaW52AAAABmNhbGxlcgAAAAAAAAAABwQAAAAHbGVhc2VJZAkABDkAAAABBQAAAAVsZWFzZQkABEwAAAACCQEAAAALQmluYXJ5RW50cnkAAAACAgAAAANiaW4FAAAAB2Fzc2V0SWQJAARMAAAAAgkBAAAADEJvb2xlYW5FbnRyeQAAAAICAAAABGJvb2wGCQAETAAAAAIJAQAAAAxJbnRlZ2VyRW50cnkAAAACAgAAAANpbnQAAAAAAAABiJQJAARMAAAAAgkBAAAAC1N0cmluZ0VudHJ5AAAAAgIAAAAHYXNzZXRJZAkAAlgAAAABBQAAAAdhc3NldElkCQAETAAAAAIJAQAAAAtTdHJpbmdFbnRyeQAAAAICAAAAB2xlYXNlSWQJAAJYAAAAAQUAAAAHbGVhc2VJZAkABEwAAAACCQEAAAALU3RyaW5nRW50cnkAAAACAgAAAANkZWwCAAAAAAkABEwAAAACCQEAAAALRGVsZXRlRW50cnkAAAABAgAAAANkZWwJAARMAAAAAgUAAAAFYXNzZXQJAARMAAAAAgkBAAAAClNwb25zb3JGZWUAAAACBQAAAAdhc3NldElkAAAAAAAAAAABCQAETAAAAAIJAQAAAAdSZWlzc3VlAAAAAwUAAAAHYXNzZXRJZAAAAAAAAAAABAcJAARMAAAAAgkBAAAABEJ1cm4AAAACBQAAAAdhc3NldElkAAAAAAAAAAADCQAETAAAAAIJAQAAAA5TY3JpcHRUcmFuc2ZlcgAAAAMIBQAAAANpbnYAAAAGY2FsbGVyAAAAAAAAAAACBQAAAAdhc3NldElkCQAETAAAAAIFAAAABWxlYXNlCQAETAAAAAIJAQAAAAtMZWFzZUNhbmNlbAAAAAEJAAQ5AAAAAQUAAAAFbGVhc2UFAAAAA25pbAAAAAD/oHwO",
+            "height": 2221195,
+            "applicationStatus": "succeeded",
+            "spentComplexity": 0
+        }"#,
+        r#"{
+            "type": 14,
+            "id": "5y8knLkSH9C6xnd7SKvsa2VzVm4kowFHJHwHUj27gdZ9",
+            "fee": 500000,
+            "feeAssetId": null,
+            "timestamp": 1662733640048,
+            "version": 3,
+            "chainId": 84,
+            "sender": "3MxtrLkrbcG28uTvmbKmhrwGrR65ooHVYvK",
+            "senderPublicKey": "CJJu3U5UL35Dhq5KGRZw2rdundAv2pPgB7GF21G3y4vt",
+            "proofs": [
+                "2SJpK6eZGETTk3N2QFHUpYZVHpgYgPRb1tYxtngquXNFaukKVQDT4EymTkfgd43jRqjfS7HNHV8ts3BBpzKPxGcA"
+            ],
+            "assetId": "8bt2MZjuUCJPmfucPfaZPTXqrxmoCHCC8gVnbjZ7bhH6",
+            "minSponsoredAssetFee": 10,
+            "height": 2221683,
+            "applicationStatus": "succeeded",
+            "spentComplexity": 0
+        }"#,
+        r#"{
+            "type": 15,
+            "id": "9FPVmrXrMB3JcdBet2tXWevViNNA8Cee8556AbkWXNMm",
+            "fee": 100000000,
+            "feeAssetId": null,
+            "timestamp": 1662730598289,
+            "version": 3,
+            "chainId": 84,
+            "sender": "3Ms6jp75u5qnfmAgWpxbt9xHv7znBp7RHnq",
+            "senderPublicKey": "ASA4fMdz5FirDREfB34PPi67QxLHMt8tvzRQDT64juiM",
+            "proofs": [
+                "63ypiZKUPUChPAYZRguwJouRMxmrY55xxttEm33e9sGzuWVPfnSBUyCtE7zVUNwCAbP9boCbZc8nQ39mCQd9X235"
+            ],
+            "assetId": "CVwsbXjXmdYF2q4RCPuQKf7sLGpzhk7BNnYsxGZZJMym",
+            "script": "base64:AgQAAAAHbWFzdGVyMQkBAAAAEWFkZHJlc3NGcm9tU3RyaW5nAAAAAQIAAAAQMzMzbWFzdGVyQWRkcmVzcwQAAAAHJG1hdGNoMAUAAAACdHgDCQAAAQAAAAIFAAAAByRtYXRjaDACAAAAE1RyYW5zZmVyVHJhbnNhY3Rpb24EAAAAAXQFAAAAByRtYXRjaDADCQAAAAAAAAIIBQAAAAF0AAAABnNlbmRlcgUAAAAHbWFzdGVyMQYJAAAAAAAAAggFAAAAAXQAAAAJcmVjaXBpZW50BQAAAAdtYXN0ZXIxAwkAAAEAAAACBQAAAAckbWF0Y2gwAgAAABdNYXNzVHJhbnNmZXJUcmFuc2FjdGlvbgQAAAACbXQFAAAAByRtYXRjaDAJAAAAAAAAAggFAAAAAm10AAAABnNlbmRlcgUAAAAHbWFzdGVyMQMJAAABAAAAAgUAAAAHJG1hdGNoMAIAAAATRXhjaGFuZ2VUcmFuc2FjdGlvbgcGFLbwIw==",
+            "height": 2221627,
+            "applicationStatus": "succeeded",
+            "spentComplexity": 0
+        }"#,
+    ];
+
+    #[test]
+    fn test_json_fixtures_roundtrip() {
+        for fixture in FIXTURES.iter() {
+            let expected: Value = serde_json::from_str(fixture).unwrap();
+            let ptx: ProvenTransaction = serde_json::from_str(fixture).unwrap();
+
+            assert_eq!(ptx.tx.id().to_string(), expected["id"]);
+            let mut sig = [0u8; SIGNATURE_LENGTH];
+            sig.copy_from_slice(&ptx.proofs[0]);
+            assert!(sig_verify(
+                &ptx.tx.to_bytes(),
+                ptx.tx.sender_public_key.to_bytes(),
+                &sig
+            ));
+
+            let json = serde_json::to_value(&ptx).unwrap();
+            for (key, value) in json.as_object().unwrap() {
+                assert_eq!(*value, expected[key], "field {} of {}", key, expected["id"]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_transfer_to_json() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let recipient =
+            Recipient::from(Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D").unwrap());
+        let tx = Transaction::new_transfer(
+            &pk,
+            &recipient,
+            None,
+            10,
+            None,
+            100000,
+            Some("atta ch me"),
+            1536000000000,
+        );
+        let ptx = tx.with_proofs(vec![vec![1, 2, 3]]);
+
+        assert_eq!(
+            serde_json::to_value(&ptx).unwrap(),
+            json!({
+                "type": 4,
+                "version": 2,
+                "id": ptx.tx.id().to_string(),
+                "senderPublicKey": "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+                "fee": 100000,
+                "feeAssetId": null,
+                "timestamp": 1536000000000u64,
+                "recipient": "3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D",
+                "assetId": null,
+                "amount": 10,
+                "attachment": "6UZYuv9obhLupx",
+                "proofs": ["Ldp"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_alias_recipient_json() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let recipient = Recipient::Alias {
+            alias: crate::util::Alias::new("merchant").unwrap(),
+            chain_id: MAINNET,
+        };
+        let tx = Transaction::new_lease(&pk, &recipient, 10, MAINNET, 100000, 1536000000000);
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(json["recipient"], "alias:W:merchant");
+        assert_eq!(serde_json::from_value::<Transaction>(json).unwrap(), tx);
+
+        let transfers = vec![(&recipient, 10)];
+        let tx = Transaction::new_mass_transfer(&pk, None, transfers, None, 200000, 1536000000000);
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(json["transfers"][0]["recipient"], "alias:W:merchant");
+        assert_eq!(serde_json::from_value::<Transaction>(json).unwrap(), tx);
+    }
+
+    #[test]
+    fn test_signature_version_json() {
+        let account = crate::account::PrivateKeyAccount::from_seed("test");
+        let pk = account.public_key();
+        let tx = Transaction::new_issue(
+            pk,
+            "coin",
+            "coin",
+            100000000,
+            8,
+            false,
+            TESTNET,
+            100000,
+            1536000000000,
+            None,
+        )
+        .with_version(Version::V1);
+        let signed = account.sign_transaction(tx);
+        let mut json = serde_json::to_value(&signed).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["signature"], json["proofs"][0]);
+        let parsed: ProvenTransaction = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(parsed, signed);
+
+        // the node returns a null chain ID, and older nodes only the signature
+        let json = json.as_object_mut().unwrap();
+        json.insert("chainId".into(), Value::Null);
+        json.insert("sender".into(), json!(pk.to_address(TESTNET).to_string()));
+        json.remove("proofs");
+        let parsed: ProvenTransaction = serde_json::from_value(json!(json)).unwrap();
+        assert_eq!(parsed, signed);
+        assert_eq!(parsed.verify(), vec![0]);
+    }
+
+    #[test]
+    fn test_data_to_json() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let bin = vec![1u8, 2, 3];
+        let int_entry = DataEntry::Integer("int".into(), 1);
+        let bin_entry = DataEntry::Binary("bin".into(), (&bin).into());
+        let tx = Transaction::new_data(&pk, vec![&int_entry, &bin_entry], 100000, 1536000000000);
+        let json = serde_json::to_value(&tx).unwrap();
+
+        assert_eq!(
+            json["data"],
+            json!([
+                {"key": "int", "type": "integer", "value": 1},
+                {"key": "bin", "type": "binary", "value": "base64:AQID"},
+            ])
+        );
+
+        let tx = Transaction::new_script(&pk, None, TESTNET, 100000, 1536000000000);
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(json["script"], Value::Null);
+        assert_eq!(json["chainId"], json!(TESTNET));
+    }
+
+    #[test]
+    fn test_data_entries_json() {
+        // as returned by /addresses/data/{address}
+        let json = json!([
+            {"key": "int", "type": "integer", "value": -12},
+            {"key": "bool", "type": "boolean", "value": false},
+            {"key": "bin", "type": "binary", "value": "base64:AQID"},
+            {"key": "str", "type": "string", "value": "some string"},
+            {"key": "deleted", "value": null},
+        ]);
+        let entries: Vec<DataEntry> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                DataEntry::Integer("int".into(), -12i64 as u64),
+                DataEntry::Boolean("bool".into(), false),
+                DataEntry::Binary("bin".into(), vec![1, 2, 3].into()),
+                DataEntry::String("str".into(), "some string".into()),
+                DataEntry::Delete("deleted".into()),
+            ]
+        );
+        let json = json.as_array().unwrap();
+        assert_eq!(
+            serde_json::to_value(&entries[1..]).unwrap(),
+            json!(json[1..])
+        );
+
+        let entry: DataEntry = serde_json::from_value(json!({"key": "deleted"})).unwrap();
+        assert_eq!(entry, DataEntry::Delete("deleted".into()));
+    }
+
+    #[test]
+    fn test_invoke_script_json() {
+        // Invoke Script transaction from a Testnet block
+        let fixture = r#"{
+            "type": 16,
+            "id": "7QT8tS7eC3Krzc65GVBdzGyfeCk8kDy9y2BTp6fMr6vx",
+            "fee": 500000,
+            "feeAssetId": null,
+            "timestamp": 1662969274915,
+            "version": 1,
+            "sender": "3MuhGCajV9HXunkyuQpwXvHTjTLaMy93g9Y",
+            "senderPublicKey": "57C4SttrQ3a2s6nHqTyPoKo6g7JFKhvojLkS3qgrVqyv",
+            "proofs": [
+                "5A473ZfYCnVdTvPC3n3A7AvfwpSe9SVJpEhYK3iCu2L891qVCaLWktsSohrtVmEUWE8XYth5hvdnrurCWKA4ajAc"
+            ],
+            "dApp": "3N4NS7d4Jo9a6F14LiFUKKYVdUkkf2eP4Zx",
+            "payment": [],
+            "call": {
+                "function": "finalizeCurrentPrice",
+                "args": [
+                    {"type": "integer", "value": 6500000},
+                    {"type": "binary", "value": "base64:lz0Lu+Wy5JmPRlBDzid1fwvf7SdK2cg3TG3GxaizEc/rf57FDCMI8qvrE0teyQGNjj1NlhGNrE98c3LSxP6WAA=="},
+                    {"type": "integer", "value": 6500000},
+                    {"type": "binary", "value": "base64:BEhxvA2BlxOm5MwYAgss1S8JrYp6O0WX4Xu/XoqVQEZOBf2SFNv//wTTo9DO2jNN+PIo3ostYSTjMABcjmi1BQ=="},
+                    {"type": "integer", "value": 6500000},
+                    {"type": "binary", "value": "base64:+ZbMzkrfzSbcdCPcOfh4/0tINICD//ZfR8EHI6CA/XMlK7UULA5yE0g2uhfqP+ffJTs6nA5ocs6OtpFUyQm3DA=="},
+                    {"type": "integer", "value": 6500000},
+                    {"type": "binary", "value": "base64:YZC++iETjT6r8wsePUm+g8AF45gZGLAJ/zhf0i6nymQs34m9xCjca9FClgBAmntjCP+zDWORF3YnygnBRvj2Dw=="},
+                    {"type": "integer", "value": 6500000},
+                    {"type": "binary", "value": "base64:A/cEQGJmlCckydAF8k4pslR2CCmRSQaJ5Ut5RF6YELVL/IZyp4G02BX2KECn7JEGvauGHsriwAW4v5rukkNpDQ=="}
+                ]
+            },
+            "applicationStatus": "succeeded"
+        }"#;
+        let expected: Value = serde_json::from_str(fixture).unwrap();
+        let ptx: ProvenTransaction = serde_json::from_str(fixture).unwrap();
+
+        assert_eq!(ptx.tx.id().to_string(), expected["id"]);
+        let mut sig = [0u8; SIGNATURE_LENGTH];
+        sig.copy_from_slice(&ptx.proofs[0]);
+        assert!(sig_verify(
+            &ptx.tx.to_bytes(),
+            ptx.tx.sender_public_key.to_bytes(),
+            &sig
+        ));
+
+        let json = serde_json::to_value(&ptx).unwrap();
+        for (key, value) in json.as_object().unwrap() {
+            assert_eq!(*value, expected[key], "field {}", key);
+        }
+
+        let mut json = expected;
+        json["dApp"] = json!("alias:T:merchant");
+        json["call"] = json!({
+            "function": "deposit",
+            "args": [
+                {"type": "boolean", "value": false},
+                {"type": "string", "value": "note"},
+                {"type": "list", "value": [{"type": "integer", "value": -1}]}
+            ]
+        });
+        json["payment"] = json!([{"amount": 10, "assetId": null}]);
+        let tx: Transaction = serde_json::from_value(json.clone()).unwrap();
+        match &tx.data {
+            InvokeScript { dapp, call, .. } => {
+                assert_eq!(dapp.to_string(), "alias:T:merchant");
+                assert_eq!(
+                    call.as_ref().unwrap().args[2],
+                    Arg::List(vec![Arg::Integer(-1)])
+                );
+            }
+            _ => panic!("not an Invoke Script transaction"),
+        }
+        let serialized = serde_json::to_value(&tx).unwrap();
+        for key in &["dApp", "call", "payment"] {
+            assert_eq!(serialized[key], json[key]);
+        }
+    }
+
+    #[test]
+    fn test_exchange_json() {
+        // Exchange transactions from Testnet, with a version 4 and a version 3 order and with two
+        // version 3 orders
+        let fixtures = [
+            r#"{
+              "type": 7,
+              "id": "Gix32QQ35e7GHay9mDwGPqC1KoeQtvxb5Z4xUUTVEsiz",
+              "fee": 300000,
+              "feeAssetId": null,
+              "timestamp": 1666571041712,
+              "version": 3,
+              "chainId": 84,
+              "sender": "3N8aZG6ZDfnh8YxS6aNcteobN8eXTWHaBBd",
+              "senderPublicKey": "8QUAqtTckM5B8gvcuP7mMswat9SjKUuafJMusEoSn1Gy",
+              "proofs": [
+                "5Y4HX6WQYnM4xZHiFjdKMisxLJEeZhkiYqHR9JHjPvDWpgs6iavfaA57qQpkBHgeZBfyPZhVYiqszxCB1G9Rhts7"
+              ],
+              "order1": {
+                "version": 4,
+                "id": "3DCDNkx3iw9UBhKfQgibxrCes1uXPeMaexpgf5kQyz18",
+                "sender": "3MzpbTjjF1ng9aLWSkq96JktGRs1FDVuDSk",
+                "senderPublicKey": "BDSyopLzAjMYvQSm4XuMA2gtjP5TPoZMWQ1sxnzTE1Y8",
+                "matcherPublicKey": "8QUAqtTckM5B8gvcuP7mMswat9SjKUuafJMusEoSn1Gy",
+                "assetPair": {
+                  "amountAsset": null,
+                  "priceAsset": "25FEqEjRkqK6yCkiT7Lz6SAYz7gUFCtxfCChnrVFD5AT"
+                },
+                "orderType": "buy",
+                "amount": 660949620,
+                "price": 15000000,
+                "timestamp": 1666571041063,
+                "expiration": 1669080241063,
+                "matcherFee": 99143,
+                "signature": "3GmuCwFTs5jcJjerkZP28aEAvFV1qqJx9QTjC9dVBVrYeqJ9pqoaB1vU1ieZmZFXTcD6jSr7JLDsKbsLKZtgcBpm",
+                "proofs": [
+                  "3GmuCwFTs5jcJjerkZP28aEAvFV1qqJx9QTjC9dVBVrYeqJ9pqoaB1vU1ieZmZFXTcD6jSr7JLDsKbsLKZtgcBpm"
+                ],
+                "matcherFeeAssetId": "25FEqEjRkqK6yCkiT7Lz6SAYz7gUFCtxfCChnrVFD5AT",
+                "eip712Signature": null,
+                "priceMode": "assetDecimals"
+              },
+              "order2": {
+                "version": 3,
+                "id": "H2EaCndcFAETGaWkPifGdNBL3scaZ53Pgm4Ha4xvg9wb",
+                "sender": "3My6wXYDaS6C86Zk3qToU8Lv24G4ueEXHcd",
+                "senderPublicKey": "FarW7tFmnVJBsHUdDe9DMJcfUESh266UDmEm1vP6P2xE",
+                "matcherPublicKey": "8QUAqtTckM5B8gvcuP7mMswat9SjKUuafJMusEoSn1Gy",
+                "assetPair": {
+                  "amountAsset": null,
+                  "priceAsset": "25FEqEjRkqK6yCkiT7Lz6SAYz7gUFCtxfCChnrVFD5AT"
+                },
+                "orderType": "sell",
+                "amount": 10000000000,
+                "price": 15000000,
+                "timestamp": 1664244861345,
+                "expiration": 1666750461345,
+                "matcherFee": 10000000,
+                "signature": "38rb8vVaYR4iqfTLvHPEQ83kkhtwjcTP4f8p8A1tSquzNF41m78GEN5Qr3Lc3k8fzeGTuV1oiPTVkoAjGvrYvmpN",
+                "proofs": [
+                  "38rb8vVaYR4iqfTLvHPEQ83kkhtwjcTP4f8p8A1tSquzNF41m78GEN5Qr3Lc3k8fzeGTuV1oiPTVkoAjGvrYvmpN"
+                ],
+                "matcherFeeAssetId": null
+              },
+              "amount": 640949620,
+              "price": 1500000000,
+              "buyMatcherFee": 96142,
+              "sellMatcherFee": 640949,
+              "height": 2285764,
+              "applicationStatus": "succeeded",
+              "spentComplexity": 0
+            }"#,
+            r#"{
+                "type": 7,
+                "id": "2dn5KbBN4itxU2eYVmFheEyZEwRC9DMVkY3MNizEnXkX",
+                "fee": 300000,
+                "feeAssetId": null,
+                "timestamp": 1662937824900,
+                "version": 3,
+                "chainId": 84,
+                "sender": "3N8aZG6ZDfnh8YxS6aNcteobN8eXTWHaBBd",
+                "senderPublicKey": "8QUAqtTckM5B8gvcuP7mMswat9SjKUuafJMusEoSn1Gy",
+                "proofs": [
+                    "3j2GPvJExkNFbLy3ym8FVKcUEyyforayBFKsGiDnLVFfBamcbcAutVcsWDNLzQkRXBqEjDK1sErqCpvrzawEssHz"
+                ],
+                "order1": {
+                    "version": 3,
+                    "id": "gDjkTEJzFCz81dFQ7p2Q7b83x7i9o11czoV47aSyByn",
+                    "sender": "3N8aZG6ZDfnh8YxS6aNcteobN8eXTWHaBBd",
+                    "senderPublicKey": "8QUAqtTckM5B8gvcuP7mMswat9SjKUuafJMusEoSn1Gy",
+                    "matcherPublicKey": "8QUAqtTckM5B8gvcuP7mMswat9SjKUuafJMusEoSn1Gy",
+                    "assetPair": {
+                        "amountAsset": "EMAMLxDnv3xiz8RXg8Btj33jcEw3wLczL3JKYYmuubpc",
+                        "priceAsset": "25FEqEjRkqK6yCkiT7Lz6SAYz7gUFCtxfCChnrVFD5AT"
+                    },
+                    "orderType": "buy",
+                    "amount": 184632448,
+                    "price": 1045840,
+                    "timestamp": 1662937824858,
+                    "expiration": 1662937914858,
+                    "matcherFee": 2272958,
+                    "signature": "mEdoNRJ5mBBSdtsXRF2M8egdQ8KfSHuKV13rMwMYht6qpKTgBo6PUhpQaJ1fzRBwYsKMytaveQ82t2uzjkawGVs",
+                    "proofs": [
+                        "mEdoNRJ5mBBSdtsXRF2M8egdQ8KfSHuKV13rMwMYht6qpKTgBo6PUhpQaJ1fzRBwYsKMytaveQ82t2uzjkawGVs"
+                    ],
+                    "matcherFeeAssetId": "EMAMLxDnv3xiz8RXg8Btj33jcEw3wLczL3JKYYmuubpc"
+                },
+                "order2": {
+                    "version": 3,
+                    "id": "3JkZeg96LHsaTxRt49nmE2FCvryf819ahCurZPbuMB5h",
+                    "sender": "3N6wAa7PMFZJu4Zrmp3avXmMnRTrRpMM9Lh",
+                    "senderPublicKey": "Cr8D7eozSzJh7XHsYTjBinPpo3SS83BrCGiBAJsjGxBo",
+                    "matcherPublicKey": "8QUAqtTckM5B8gvcuP7mMswat9SjKUuafJMusEoSn1Gy",
+                    "assetPair": {
+                        "amountAsset": "EMAMLxDnv3xiz8RXg8Btj33jcEw3wLczL3JKYYmuubpc",
+                        "priceAsset": "25FEqEjRkqK6yCkiT7Lz6SAYz7gUFCtxfCChnrVFD5AT"
+                    },
+                    "orderType": "sell",
+                    "amount": 246298520137,
+                    "price": 1045840,
+                    "timestamp": 1660724297939,
+                    "expiration": 1663229897939,
+                    "matcherFee": 246298521,
+                    "signature": "2zBDZnuL6R3ra9SUUTv5K298WK53MENnmospx5ZkfdK82kxrtojVXNgvbTvttTSJCdc5AqhUUYmuJtA1k9A2fhdW",
+                    "proofs": [
+                        "2zBDZnuL6R3ra9SUUTv5K298WK53MENnmospx5ZkfdK82kxrtojVXNgvbTvttTSJCdc5AqhUUYmuJtA1k9A2fhdW",
+                        "5pq37TtEV2eg76GTMLyVaELKb6Wa45fTR1LLZKH4ntfn9urC3g9tNNmyqfqP7BKhZuNizAJVBKL2yKqopbu2j5bY"
+                    ],
+                    "matcherFeeAssetId": "EMAMLxDnv3xiz8RXg8Btj33jcEw3wLczL3JKYYmuubpc"
+                },
+                "amount": 184632353,
+                "price": 104584000,
+                "buyMatcherFee": 2272956,
+                "sellMatcherFee": 0,
+                "applicationStatus": "succeeded"
+            }"#,
+        ];
+        for fixture in fixtures.iter() {
+            let expected: Value = serde_json::from_str(fixture).unwrap();
+            let ptx: ProvenTransaction = serde_json::from_str(fixture).unwrap();
+
+            assert_eq!(ptx.tx.id().to_string(), expected["id"]);
+            let mut sig = [0u8; SIGNATURE_LENGTH];
+            sig.copy_from_slice(&ptx.proofs[0]);
+            assert!(sig_verify(
+                &ptx.tx.to_bytes(),
+                ptx.tx.sender_public_key.to_bytes(),
+                &sig
+            ));
+
+            let (order1, order2) = match &ptx.tx.data {
+                Exchange { order1, order2, .. } => (order1, order2),
+                _ => panic!("not an Exchange transaction"),
+            };
+            for (order, key) in &[(order1, "order1"), (order2, "order2")] {
+                assert_eq!(order.order.id().to_string(), expected[key]["id"]);
+                assert_eq!(order.order.chain_id, TESTNET);
+                sig.copy_from_slice(&order.proofs[0]);
+                assert!(sig_verify(
+                    &order.order.to_bytes(),
+                    order.order.sender_public_key.to_bytes(),
+                    &sig
+                ));
+            }
+
+            let json = serde_json::to_value(&ptx).unwrap();
+            for (key, value) in json.as_object().unwrap() {
+                if key.starts_with("order") {
+                    for (order_key, order_value) in value.as_object().unwrap() {
+                        assert_eq!(
+                            *order_value, expected[key][order_key],
+                            "field {}",
+                            order_key
+                        );
+                    }
+                } else {
+                    assert_eq!(*value, expected[key], "field {}", key);
+                }
+            }
+        }
+
+        let mut json: Value = serde_json::from_str(fixtures[0]).unwrap();
+        json["order1"]["orderType"] = json!("bid");
+        let err = serde_json::from_value::<Transaction>(json).unwrap_err();
+        assert_eq!(err.to_string(), "unknown order type `bid`");
+    }
+
+    #[test]
+    fn test_update_asset_info_json() {
+        // Update Asset Info transaction from Stagenet
+        let fixture = r#"{
+            "type": 17,
+            "id": "A8xxTmhe8PDiggtJtK64maaseq7kp35tHqJHGjZ98xmo",
+            "fee": 100000,
+            "feeAssetId": null,
+            "timestamp": 1662814074142,
+            "version": 3,
+            "chainId": 83,
+            "sender": "3MZZFMeZJu5ZG62ngZuSPNp14naps6FPJ2T",
+            "senderPublicKey": "CJJu3U5UL35Dhq5KGRZw2rdundAv2pPgB7GF21G3y4vt",
+            "proofs": [
+                "4bTrycSB8oxor5vKRcoyuwHusg6osncdZGxRh2mX6rA8UHrdCMbchej6G9riib9hjVJ345mTHm2cu1P5Wombdmad"
+            ],
+            "assetId": "7qhc24Cq53DiaHUzmcaYMUKq8kidaVW8ZAvKrTtADozG",
+            "name": "UpdatedAsset",
+            "description": "updated description",
+            "height": 1262355,
+            "applicationStatus": "succeeded",
+            "spentComplexity": 0
+        }"#;
+        let expected: Value = serde_json::from_str(fixture).unwrap();
+        let ptx: ProvenTransaction = serde_json::from_str(fixture).unwrap();
+
+        assert_eq!(ptx.tx.id().to_string(), expected["id"]);
+        let mut sig = [0u8; SIGNATURE_LENGTH];
+        sig.copy_from_slice(&ptx.proofs[0]);
+        assert!(sig_verify(
+            &ptx.tx.to_bytes(),
+            ptx.tx.sender_public_key.to_bytes(),
+            &sig
+        ));
+
+        let json = serde_json::to_value(&ptx).unwrap();
+        for (key, value) in json.as_object().unwrap() {
+            assert_eq!(*value, expected[key], "field {}", key);
+        }
+    }
+
+    #[test]
+    fn test_json_errors() {
+        let mut json: Value = serde_json::from_str(FIXTURES[1]).unwrap();
+        json["recipient"] = json!("alias:T:a");
+        let err = serde_json::from_value::<ProvenTransaction>(json).unwrap_err();
+        assert_eq!(err.to_string(), "invalid field `recipient`: invalid alias");
+
+        let mut json: Value = serde_json::from_str(FIXTURES[1]).unwrap();
+        json["type"] = json!(2);
+        let err = serde_json::from_value::<Transaction>(json).unwrap_err();
+        assert_eq!(err.to_string(), "unknown transaction type 2");
+
+        let mut json: Value = serde_json::from_str(FIXTURES[0]).unwrap();
+        json.as_object_mut().unwrap().remove("chainId");
+        let err = serde_json::from_value::<Transaction>(json).unwrap_err();
+        assert_eq!(err.to_string(), "missing field `chainId`");
+
+        let mut json: Value = serde_json::from_str(FIXTURES[0]).unwrap();
+        json["script"] = json!("AQa3b8tH");
+        let err = serde_json::from_value::<Transaction>(json).unwrap_err();
+        assert_eq!(err.to_string(), "invalid base64 value of field `script`");
+    }
+}
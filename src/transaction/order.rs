@@ -0,0 +1,432 @@
+use crate::account::{blake_hash, PublicKeyAccount};
+use crate::bytebuffer::{Buffer, Reader};
+use crate::transaction::{Asset, Hash, ParseError, Version, HASH_LENGTH};
+
+use ed25519_dalek::SIGNATURE_LENGTH;
+use std::fmt;
+
+/// The Order ID is a [`struct@Hash`]
+pub type OrderId = Hash;
+
+/// Order side: whether the order buys or sells the amount asset
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OrderType {
+    Buy = 0,
+    Sell = 1,
+}
+
+impl fmt::Display for OrderType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderType::Buy => write!(f, "buy"),
+            OrderType::Sell => write!(f, "sell"),
+        }
+    }
+}
+
+/// How the price of a version 4 order is scaled
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PriceMode {
+    /// Fixed decimals for orders before version 4, asset decimals for version 4
+    Default = 0,
+    /// The price is multiplied by 10^8
+    FixedDecimals = 1,
+    /// The price is multiplied by 10^(8 + price asset decimals - amount asset decimals)
+    AssetDecimals = 2,
+}
+
+impl fmt::Display for PriceMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PriceMode::Default => write!(f, "default"),
+            PriceMode::FixedDecimals => write!(f, "fixedDecimals"),
+            PriceMode::AssetDecimals => write!(f, "assetDecimals"),
+        }
+    }
+}
+
+/// Order to buy or sell the amount asset of an asset pair for the price asset, placed with a
+/// matcher. Assets are `None` for WAVES.
+///
+/// Orders of versions 1 to 3 are signed in the legacy binary format, which contains neither the
+/// chain ID nor the price mode; the matcher fee asset is part of it since version 3. Version 4
+/// orders are signed as a protobuf `Order` message.
+/// # Usage
+/// ```
+/// use wavesplatform::account::{PrivateKeyAccount, TESTNET};
+/// use wavesplatform::transaction::*;
+/// let account = PrivateKeyAccount::from_seed("seed");
+/// let matcher = PrivateKeyAccount::from_seed("matcher");
+/// let price_asset = Asset::from_string("25FEqEjRkqK6yCkiT7Lz6SAYz7gUFCtxfCChnrVFD5AT").unwrap();
+/// let order = Order::new(
+///     account.public_key(),
+///     matcher.public_key(),
+///     None,
+///     Some(&price_asset),
+///     OrderType::Buy,
+///     100000000,
+///     15000000,
+///     1536000000000,
+///     1538000000000,
+///     300000,
+///     None,
+///     TESTNET,
+/// );
+/// let signed_order = account.sign_order(order);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Order {
+    pub version: u8,
+    pub chain_id: u8,
+    pub sender_public_key: PublicKeyAccount,
+    pub matcher_public_key: PublicKeyAccount,
+    pub amount_asset: Option<Asset>,
+    pub price_asset: Option<Asset>,
+    pub order_type: OrderType,
+    pub price_mode: PriceMode,
+    pub amount: u64,
+    pub price: u64,
+    pub timestamp: u64,
+    pub expiration: u64,
+    pub matcher_fee: u64,
+    pub matcher_fee_asset: Option<Asset>,
+}
+
+impl Order {
+    /// Creates a version 3 order with the default price mode
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        sender_public_key: &PublicKeyAccount,
+        matcher_public_key: &PublicKeyAccount,
+        amount_asset: Option<&Asset>,
+        price_asset: Option<&Asset>,
+        order_type: OrderType,
+        amount: u64,
+        price: u64,
+        timestamp: u64,
+        expiration: u64,
+        matcher_fee: u64,
+        matcher_fee_asset: Option<&Asset>,
+        chain_id: u8,
+    ) -> Order {
+        Order {
+            version: Version::V3 as u8,
+            chain_id,
+            sender_public_key: *sender_public_key,
+            matcher_public_key: *matcher_public_key,
+            amount_asset: amount_asset.copied(),
+            price_asset: price_asset.copied(),
+            order_type,
+            price_mode: PriceMode::Default,
+            amount,
+            price,
+            timestamp,
+            expiration,
+            matcher_fee,
+            matcher_fee_asset: matcher_fee_asset.copied(),
+        }
+    }
+
+    /// Sets the order version. Versions 1 and 2 can only have the matcher fee in WAVES, and
+    /// only version 4 has a price mode.
+    pub fn with_version(mut self, version: Version) -> Order {
+        self.version = version as u8;
+        self
+    }
+
+    /// Returns the bytes to sign: the legacy binary body or, for version 4, the protobuf `Order`
+    /// message without proofs.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        if self.version >= Version::V4 as u8 {
+            return self.to_protobuf();
+        }
+
+        let mut buf = Buffer::new();
+        if self.version > Version::V1 as u8 {
+            buf.byte(self.version);
+        }
+        buf.bytes(self.sender_public_key.to_bytes())
+            .bytes(self.matcher_public_key.to_bytes())
+            .asset_opt(self.amount_asset.as_ref())
+            .asset_opt(self.price_asset.as_ref())
+            .byte(self.order_type as u8)
+            .long(self.price)
+            .long(self.amount)
+            .long(self.timestamp)
+            .long(self.expiration)
+            .long(self.matcher_fee);
+        if self.version == Version::V3 as u8 {
+            buf.asset_opt(self.matcher_fee_asset.as_ref());
+        }
+        Vec::from(buf.as_slice())
+    }
+
+    /// Returns order ID
+    pub fn id(&self) -> OrderId {
+        let mut id = [0u8; HASH_LENGTH];
+        id.copy_from_slice(&blake_hash(&self.to_bytes()));
+        OrderId::new(id)
+    }
+
+    /// Returns a ProvenOrder with the given proofs
+    pub fn with_proofs(self, proofs: Vec<Vec<u8>>) -> ProvenOrder {
+        ProvenOrder {
+            order: self,
+            proofs,
+        }
+    }
+
+    /// Reads the legacy body of an order of the given version
+    fn read(reader: &mut Reader, version: u8, chain_id: u8) -> Result<Order, ParseError> {
+        if version > Version::V1 as u8 && reader.byte()? != version {
+            return Err(ParseError::UnsupportedOrderVersion(version));
+        }
+        let sender_public_key = reader.public_key()?;
+        let matcher_public_key = reader.public_key()?;
+        let amount_asset = reader.asset_opt()?;
+        let price_asset = reader.asset_opt()?;
+        let order_type = match reader.byte()? {
+            0 => OrderType::Buy,
+            1 => OrderType::Sell,
+            flag => return Err(ParseError::InvalidFlag(flag)),
+        };
+        let price = reader.long()?;
+        let amount = reader.long()?;
+        let timestamp = reader.long()?;
+        let expiration = reader.long()?;
+        let matcher_fee = reader.long()?;
+        let matcher_fee_asset = if version == Version::V3 as u8 {
+            reader.asset_opt()?
+        } else {
+            None
+        };
+        Ok(Order {
+            version,
+            chain_id,
+            sender_public_key,
+            matcher_public_key,
+            amount_asset,
+            price_asset,
+            order_type,
+            price_mode: PriceMode::Default,
+            amount,
+            price,
+            timestamp,
+            expiration,
+            matcher_fee,
+            matcher_fee_asset,
+        })
+    }
+}
+
+/// Order with proofs. A version 1 order has exactly one proof, its signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvenOrder {
+    pub order: Order,
+    pub proofs: Vec<Vec<u8>>,
+}
+
+impl ProvenOrder {
+    /// Returns the signed order bytes: the legacy body followed by the signature (version 1) or
+    /// the proofs (versions 2 and 3), or, for version 4, the protobuf `Order` message with proofs.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        if self.order.version >= Version::V4 as u8 {
+            return self.to_protobuf();
+        }
+
+        let mut buf = Buffer::new();
+        buf.bytes(&self.order.to_bytes());
+        if self.order.version == Version::V1 as u8 {
+            buf.bytes(self.proofs.first().map(Vec::as_slice).unwrap_or_default());
+        } else {
+            buf.proofs(&self.proofs);
+        }
+        Vec::from(buf.as_slice())
+    }
+
+    /// Writes the order the way a legacy Exchange transaction embeds it: the length of the
+    /// signed bytes, a version 1 mark, and the signed bytes
+    pub(crate) fn write(&self, buf: &mut Buffer) {
+        let bytes = self.to_bytes();
+        buf.int(bytes.len() as u32);
+        if self.order.version == Version::V1 as u8 {
+            buf.byte(Version::V1 as u8);
+        }
+        buf.bytes(&bytes);
+    }
+
+    /// Reads an order written by [`ProvenOrder::write`]
+    pub(crate) fn read(reader: &mut Reader, chain_id: u8) -> Result<ProvenOrder, ParseError> {
+        let len = reader.int()? as usize;
+        let version = match reader.peek()? {
+            1 => reader.byte()?,
+            version @ 2..=3 => version,
+            version => return Err(ParseError::UnsupportedOrderVersion(version)),
+        };
+        ProvenOrder::read_signed(reader.bytes(len)?, version, chain_id)
+    }
+
+    /// Reads the signed bytes of a legacy order of the given version, see
+    /// [`ProvenOrder::to_bytes`]
+    pub(crate) fn read_signed(
+        bytes: &[u8],
+        version: u8,
+        chain_id: u8,
+    ) -> Result<ProvenOrder, ParseError> {
+        let mut reader = Reader::new(bytes);
+        let order = Order::read(&mut reader, version, chain_id)?;
+        let proofs = if version == Version::V1 as u8 {
+            vec![reader.bytes(SIGNATURE_LENGTH)?.to_vec()]
+        } else {
+            reader.proofs()?
+        };
+        reader.finish()?;
+        Ok(ProvenOrder { order, proofs })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::account::{PrivateKeyAccount, TESTNET};
+    use crate::util::sig_verify;
+
+    use base58::FromBase58;
+
+    #[test]
+    fn test_order_bytes() {
+        let sender = PrivateKeyAccount::from_seed("test");
+        let matcher = PublicKeyAccount([1u8; 32]);
+        let asset = Asset::new([2u8; 32]);
+        let order = Order::new(
+            sender.public_key(),
+            &matcher,
+            Some(&asset),
+            None,
+            OrderType::Sell,
+            100,
+            200,
+            1536000000000,
+            1538000000000,
+            300000,
+            Some(&asset),
+            TESTNET,
+        );
+
+        let bytes = order.to_bytes();
+        assert_eq!(bytes[0], 3);
+        assert_eq!(bytes[1..33], sender.public_key().to_bytes()[..]);
+        assert_eq!(bytes[65..67], [1, 2]);
+        assert_eq!(bytes.len(), 1 + 32 + 32 + 33 + 1 + 1 + 5 * 8 + 33);
+        assert_eq!(order.id().to_bytes(), blake_hash(&bytes).as_slice());
+
+        let signed = sender.sign_order(order.clone());
+        let mut sig = [0u8; SIGNATURE_LENGTH];
+        sig.copy_from_slice(&signed.proofs[0]);
+        assert!(sig_verify(&bytes, sender.public_key().to_bytes(), &sig));
+
+        for version in &[Version::V1, Version::V2, Version::V3] {
+            let signed = sender.sign_order(order.clone().with_version(*version));
+            let mut buf = Buffer::new();
+            signed.write(&mut buf);
+            let mut reader = Reader::new(buf.as_slice());
+            let mut expected = signed.clone();
+            if *version != Version::V3 {
+                expected.order.matcher_fee_asset = None;
+            }
+            assert_eq!(ProvenOrder::read(&mut reader, TESTNET), Ok(expected));
+            reader.finish().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_order_v1_bytes() {
+        let matcher = PublicKeyAccount([1u8; 32]);
+        let order = Order::new(
+            &matcher,
+            &matcher,
+            None,
+            None,
+            OrderType::Buy,
+            1,
+            1,
+            0,
+            0,
+            0,
+            None,
+            TESTNET,
+        )
+        .with_version(Version::V1);
+        let signed = order.with_proofs(vec![vec![7u8; SIGNATURE_LENGTH]]);
+
+        let mut buf = Buffer::new();
+        signed.write(&mut buf);
+        let bytes = buf.as_slice();
+        let len = 32 + 32 + 1 + 1 + 1 + 5 * 8 + SIGNATURE_LENGTH;
+        assert_eq!(bytes[..5], [0, 0, 0, len as u8, 1]);
+        assert_eq!(bytes[5..37], [1u8; 32]);
+        assert_eq!(
+            bytes[bytes.len() - SIGNATURE_LENGTH..],
+            [7u8; SIGNATURE_LENGTH]
+        );
+
+        let mut truncated = Reader::new(&bytes[..bytes.len() - 1]);
+        assert_eq!(
+            ProvenOrder::read(&mut truncated, TESTNET),
+            Err(ParseError::UnexpectedEnd)
+        );
+        let mut unknown = bytes.to_vec();
+        unknown[4] = 4;
+        assert_eq!(
+            ProvenOrder::read(&mut Reader::new(&unknown), TESTNET),
+            Err(ParseError::UnsupportedOrderVersion(4))
+        );
+    }
+
+    #[test]
+    fn test_order_v4_signature() {
+        // order of an Exchange transaction on Testnet
+        let sender = "BDSyopLzAjMYvQSm4XuMA2gtjP5TPoZMWQ1sxnzTE1Y8"
+            .from_base58()
+            .unwrap();
+        let matcher = "8QUAqtTckM5B8gvcuP7mMswat9SjKUuafJMusEoSn1Gy"
+            .from_base58()
+            .unwrap();
+        let usdn = Asset::from_string("25FEqEjRkqK6yCkiT7Lz6SAYz7gUFCtxfCChnrVFD5AT").unwrap();
+        let mut sender_pk = [0u8; 32];
+        sender_pk.copy_from_slice(&sender);
+        let mut matcher_pk = [0u8; 32];
+        matcher_pk.copy_from_slice(&matcher);
+        let order = Order {
+            version: Version::V4 as u8,
+            price_mode: PriceMode::AssetDecimals,
+            ..Order::new(
+                &PublicKeyAccount(sender_pk),
+                &PublicKeyAccount(matcher_pk),
+                None,
+                Some(&usdn),
+                OrderType::Buy,
+                660949620,
+                15000000,
+                1666571041063,
+                1669080241063,
+                99143,
+                Some(&usdn),
+                TESTNET,
+            )
+        };
+
+        assert_eq!(
+            order.id().to_string(),
+            "3DCDNkx3iw9UBhKfQgibxrCes1uXPeMaexpgf5kQyz18"
+        );
+        let mut sig = [0u8; SIGNATURE_LENGTH];
+        sig.copy_from_slice(
+            &"3GmuCwFTs5jcJjerkZP28aEAvFV1qqJx9QTjC9dVBVrYeqJ9pqoaB1vU1ieZmZFXTcD6jSr7JLDsKbsLKZtgcBpm"
+                .from_base58()
+                .unwrap(),
+        );
+        assert!(sig_verify(&order.to_bytes(), &sender_pk, &sig));
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+/// List of errors in parsing a [`Transaction`](crate::transaction::Transaction) from bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input ended before the transaction was fully read
+    UnexpectedEnd,
+    /// The input contains extra bytes after the transaction
+    TrailingBytes(usize),
+    /// The transaction type is not known to this library
+    UnknownType(u8),
+    /// The transaction type is known, but its version is not supported (type, version)
+    UnsupportedVersion(u8, u8),
+    /// A boolean or optional field flag is neither 0 nor 1
+    InvalidFlag(u8),
+    /// A string field is not valid UTF-8
+    InvalidUtf8,
+    /// A data entry has an unknown value type
+    UnknownDataType(u8),
+    /// The recipient starts with an unknown recipient marker byte
+    UnsupportedRecipient(u8),
+    /// The proofs block has an unknown version
+    UnsupportedProofsVersion(u8),
+    /// An alias recipient is not a valid alias
+    InvalidAlias,
+    /// A function call contains a RIDE expression other than a constant or a list of constants
+    /// (its tag)
+    UnsupportedExpression(u8),
+    /// The order version is not supported
+    UnsupportedOrderVersion(u8),
+    /// The protobuf message is malformed or lacks a required field
+    InvalidProtobuf(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ParseError::TrailingBytes(count) => write!(f, "{} trailing bytes", count),
+            ParseError::UnknownType(type_id) => write!(f, "unknown transaction type {}", type_id),
+            ParseError::UnsupportedVersion(type_id, version) => write!(
+                f,
+                "unsupported version {} of transaction type {}",
+                version, type_id
+            ),
+            ParseError::InvalidFlag(flag) => write!(f, "invalid flag {}", flag),
+            ParseError::InvalidUtf8 => write!(f, "invalid UTF-8 string"),
+            ParseError::UnknownDataType(value_type) => {
+                write!(f, "unknown data entry type {}", value_type)
+            }
+            ParseError::UnsupportedRecipient(marker) => {
+                write!(f, "unknown recipient marker {}", marker)
+            }
+            ParseError::UnsupportedProofsVersion(version) => {
+                write!(f, "unsupported proofs version {}", version)
+            }
+            ParseError::InvalidAlias => write!(f, "invalid alias"),
+            ParseError::UnsupportedExpression(tag) => {
+                write!(f, "unsupported RIDE expression {}", tag)
+            }
+            ParseError::UnsupportedOrderVersion(version) => {
+                write!(f, "unsupported order version {}", version)
+            }
+            ParseError::InvalidProtobuf(message) => write!(f, "invalid protobuf: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
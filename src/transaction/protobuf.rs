@@ -0,0 +1,1119 @@
+use crate::account::{secure_hash, Address, PublicKeyAccount, ADDRESS_LENGTH, ADDRESS_VERSION};
+use crate::bytebuffer::{Buffer, Reader};
+use crate::transaction::{
+    Asset, DataEntry, FunctionCall, Hash, Order, OrderType, ParseError, PriceMode, ProvenOrder,
+    ProvenTransaction, Recipient, Transaction, TransactionData, Type, Version, HASH_LENGTH,
+};
+use crate::util::Alias;
+
+use ed25519_dalek::PUBLIC_KEY_LENGTH;
+use prost::Message;
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+/// Length of the public key hash, i.e. of the part of the address between the chain ID and the
+/// checksum
+const PUBLIC_KEY_HASH_LENGTH: usize = 20;
+
+/// Messages of the `waves` protobuf package, limited to the transaction types this library
+/// supports. Fields are declared in tag order so that the encoding matches the node's.
+#[allow(clippy::enum_variant_names)]
+mod waves {
+    use prost::{Message, Oneof};
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct SignedTransaction {
+        #[prost(message, optional, tag = "1")]
+        pub waves_transaction: Option<Transaction>,
+        #[prost(bytes = "vec", repeated, tag = "2")]
+        pub proofs: Vec<Vec<u8>>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct Transaction {
+        #[prost(int32, tag = "1")]
+        pub chain_id: i32,
+        #[prost(bytes = "vec", tag = "2")]
+        pub sender_public_key: Vec<u8>,
+        #[prost(message, optional, tag = "3")]
+        pub fee: Option<Amount>,
+        #[prost(int64, tag = "4")]
+        pub timestamp: i64,
+        #[prost(int32, tag = "5")]
+        pub version: i32,
+        #[prost(
+            oneof = "Data",
+            tags = "103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117"
+        )]
+        pub data: Option<Data>,
+    }
+
+    #[derive(Clone, PartialEq, Oneof)]
+    pub enum Data {
+        #[prost(message, tag = "103")]
+        Issue(IssueTransactionData),
+        #[prost(message, tag = "104")]
+        Transfer(TransferTransactionData),
+        #[prost(message, tag = "105")]
+        Reissue(ReissueTransactionData),
+        #[prost(message, tag = "106")]
+        Burn(BurnTransactionData),
+        #[prost(message, tag = "107")]
+        Exchange(ExchangeTransactionData),
+        #[prost(message, tag = "108")]
+        Lease(LeaseTransactionData),
+        #[prost(message, tag = "109")]
+        LeaseCancel(LeaseCancelTransactionData),
+        #[prost(message, tag = "110")]
+        CreateAlias(CreateAliasTransactionData),
+        #[prost(message, tag = "111")]
+        MassTransfer(MassTransferTransactionData),
+        #[prost(message, tag = "112")]
+        DataTransaction(DataTransactionData),
+        #[prost(message, tag = "113")]
+        SetScript(SetScriptTransactionData),
+        #[prost(message, tag = "114")]
+        SponsorFee(SponsorFeeTransactionData),
+        #[prost(message, tag = "115")]
+        SetAssetScript(SetAssetScriptTransactionData),
+        #[prost(message, tag = "116")]
+        InvokeScript(InvokeScriptTransactionData),
+        #[prost(message, tag = "117")]
+        UpdateAssetInfo(UpdateAssetInfoTransactionData),
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct Amount {
+        #[prost(bytes = "vec", tag = "1")]
+        pub asset_id: Vec<u8>,
+        #[prost(int64, tag = "2")]
+        pub amount: i64,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct Recipient {
+        #[prost(oneof = "recipient::Recipient", tags = "1, 2")]
+        pub recipient: Option<recipient::Recipient>,
+    }
+
+    pub mod recipient {
+        use prost::Oneof;
+
+        #[derive(Clone, PartialEq, Oneof)]
+        pub enum Recipient {
+            #[prost(bytes, tag = "1")]
+            PublicKeyHash(Vec<u8>),
+            #[prost(string, tag = "2")]
+            Alias(String),
+        }
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct IssueTransactionData {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(string, tag = "2")]
+        pub description: String,
+        #[prost(int64, tag = "3")]
+        pub amount: i64,
+        #[prost(int32, tag = "4")]
+        pub decimals: i32,
+        #[prost(bool, tag = "5")]
+        pub reissuable: bool,
+        #[prost(bytes = "vec", tag = "6")]
+        pub script: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct TransferTransactionData {
+        #[prost(message, optional, tag = "1")]
+        pub recipient: Option<Recipient>,
+        #[prost(message, optional, tag = "2")]
+        pub amount: Option<Amount>,
+        #[prost(bytes = "vec", tag = "3")]
+        pub attachment: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct ReissueTransactionData {
+        #[prost(message, optional, tag = "1")]
+        pub asset_amount: Option<Amount>,
+        #[prost(bool, tag = "2")]
+        pub reissuable: bool,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct BurnTransactionData {
+        #[prost(message, optional, tag = "1")]
+        pub asset_amount: Option<Amount>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct ExchangeTransactionData {
+        #[prost(int64, tag = "1")]
+        pub amount: i64,
+        #[prost(int64, tag = "2")]
+        pub price: i64,
+        #[prost(int64, tag = "3")]
+        pub buy_matcher_fee: i64,
+        #[prost(int64, tag = "4")]
+        pub sell_matcher_fee: i64,
+        #[prost(message, repeated, tag = "5")]
+        pub orders: Vec<Order>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct AssetPair {
+        #[prost(bytes = "vec", tag = "1")]
+        pub amount_asset_id: Vec<u8>,
+        #[prost(bytes = "vec", tag = "2")]
+        pub price_asset_id: Vec<u8>,
+    }
+
+    /// Order signed by its sender. Orders signed with an EIP-712 signature (tag 13) instead of a
+    /// sender public key are not supported; the order side and price mode enums are encoded as
+    /// their numbers.
+    #[derive(Clone, PartialEq, Message)]
+    pub struct Order {
+        #[prost(int32, tag = "1")]
+        pub chain_id: i32,
+        #[prost(bytes = "vec", tag = "2")]
+        pub sender_public_key: Vec<u8>,
+        #[prost(bytes = "vec", tag = "3")]
+        pub matcher_public_key: Vec<u8>,
+        #[prost(message, optional, tag = "4")]
+        pub asset_pair: Option<AssetPair>,
+        #[prost(int32, tag = "5")]
+        pub order_side: i32,
+        #[prost(int64, tag = "6")]
+        pub amount: i64,
+        #[prost(int64, tag = "7")]
+        pub price: i64,
+        #[prost(int64, tag = "8")]
+        pub timestamp: i64,
+        #[prost(int64, tag = "9")]
+        pub expiration: i64,
+        #[prost(message, optional, tag = "10")]
+        pub matcher_fee: Option<Amount>,
+        #[prost(int32, tag = "11")]
+        pub version: i32,
+        #[prost(bytes = "vec", repeated, tag = "12")]
+        pub proofs: Vec<Vec<u8>>,
+        #[prost(int32, tag = "14")]
+        pub price_mode: i32,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct LeaseTransactionData {
+        #[prost(message, optional, tag = "1")]
+        pub recipient: Option<Recipient>,
+        #[prost(int64, tag = "2")]
+        pub amount: i64,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct LeaseCancelTransactionData {
+        #[prost(bytes = "vec", tag = "1")]
+        pub lease_id: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct CreateAliasTransactionData {
+        #[prost(string, tag = "1")]
+        pub alias: String,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct MassTransferTransactionData {
+        #[prost(bytes = "vec", tag = "1")]
+        pub asset_id: Vec<u8>,
+        #[prost(message, repeated, tag = "2")]
+        pub transfers: Vec<mass_transfer::Transfer>,
+        #[prost(bytes = "vec", tag = "3")]
+        pub attachment: Vec<u8>,
+    }
+
+    pub mod mass_transfer {
+        use prost::Message;
+
+        #[derive(Clone, PartialEq, Message)]
+        pub struct Transfer {
+            #[prost(message, optional, tag = "1")]
+            pub recipient: Option<super::Recipient>,
+            #[prost(int64, tag = "2")]
+            pub amount: i64,
+        }
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct DataTransactionData {
+        #[prost(message, repeated, tag = "1")]
+        pub data: Vec<DataEntry>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct DataEntry {
+        #[prost(string, tag = "1")]
+        pub key: String,
+        #[prost(oneof = "data_entry::Value", tags = "10, 11, 12, 13")]
+        pub value: Option<data_entry::Value>,
+    }
+
+    pub mod data_entry {
+        use prost::Oneof;
+
+        #[derive(Clone, PartialEq, Oneof)]
+        pub enum Value {
+            #[prost(int64, tag = "10")]
+            IntValue(i64),
+            #[prost(bool, tag = "11")]
+            BoolValue(bool),
+            #[prost(bytes, tag = "12")]
+            BinaryValue(Vec<u8>),
+            #[prost(string, tag = "13")]
+            StringValue(String),
+        }
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct SetScriptTransactionData {
+        #[prost(bytes = "vec", tag = "1")]
+        pub script: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct SponsorFeeTransactionData {
+        #[prost(message, optional, tag = "1")]
+        pub min_fee: Option<Amount>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct SetAssetScriptTransactionData {
+        #[prost(bytes = "vec", tag = "1")]
+        pub asset_id: Vec<u8>,
+        #[prost(bytes = "vec", tag = "2")]
+        pub script: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct InvokeScriptTransactionData {
+        #[prost(message, optional, tag = "1")]
+        pub d_app: Option<Recipient>,
+        #[prost(bytes = "vec", tag = "2")]
+        pub function_call: Vec<u8>,
+        #[prost(message, repeated, tag = "3")]
+        pub payments: Vec<Amount>,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    pub struct UpdateAssetInfoTransactionData {
+        #[prost(bytes = "vec", tag = "1")]
+        pub asset_id: Vec<u8>,
+        #[prost(string, tag = "2")]
+        pub name: String,
+        #[prost(string, tag = "3")]
+        pub description: String,
+    }
+}
+
+use waves::data_entry::Value;
+use waves::recipient::Recipient as RecipientValue;
+
+impl From<prost::DecodeError> for ParseError {
+    fn from(err: prost::DecodeError) -> ParseError {
+        ParseError::InvalidProtobuf(err.to_string())
+    }
+}
+
+fn invalid(message: &str) -> ParseError {
+    ParseError::InvalidProtobuf(message.to_string())
+}
+
+fn amount(asset: Option<&Asset>, amount: u64) -> waves::Amount {
+    waves::Amount {
+        asset_id: asset_id(asset),
+        amount: amount as i64,
+    }
+}
+
+fn recipient(recipient: &Recipient) -> Option<waves::Recipient> {
+    let value = match recipient {
+        Recipient::Address(address) => RecipientValue::PublicKeyHash(
+            address.to_bytes()[2..2 + PUBLIC_KEY_HASH_LENGTH].to_vec(),
+        ),
+        Recipient::Alias { alias, .. } => RecipientValue::Alias(alias.to_string()),
+    };
+    Some(waves::Recipient {
+        recipient: Some(value),
+    })
+}
+
+/// Serializes an optional function call the way the legacy binary format does
+fn function_call(call: &Option<FunctionCall>) -> Vec<u8> {
+    let mut buf = Buffer::new();
+    match call {
+        Some(call) => buf.byte(1).function_call(call),
+        None => buf.byte(0),
+    };
+    buf.as_slice().to_vec()
+}
+
+fn bytes_opt(bytes: &Option<Cow<[u8]>>) -> Vec<u8> {
+    bytes.as_deref().map(<[u8]>::to_vec).unwrap_or_default()
+}
+
+fn data_entry(entry: &DataEntry) -> waves::DataEntry {
+    let (key, value) = match entry {
+        DataEntry::Integer(key, value) => (key, Some(Value::IntValue(*value as i64))),
+        DataEntry::Boolean(key, value) => (key, Some(Value::BoolValue(*value))),
+        DataEntry::Binary(key, value) => (key, Some(Value::BinaryValue(value.to_vec()))),
+        DataEntry::String(key, value) => (key, Some(Value::StringValue(value.to_string()))),
+        DataEntry::Delete(key) => (key, None),
+    };
+    waves::DataEntry {
+        key: key.to_string(),
+        value,
+    }
+}
+
+/// Returns the size of the `DataTransactionData` message with the given entries, which the fee of
+/// a protobuf Data transaction depends on
+pub(crate) fn data_payload_size(data: &[DataEntry]) -> usize {
+    waves::DataTransactionData {
+        data: data.iter().map(data_entry).collect(),
+    }
+    .encoded_len()
+}
+
+fn asset_id(asset: Option<&Asset>) -> Vec<u8> {
+    asset.map(|a| a.to_bytes().to_vec()).unwrap_or_default()
+}
+
+fn order(order: &Order, proofs: Vec<Vec<u8>>) -> waves::Order {
+    waves::Order {
+        chain_id: i32::from(order.chain_id),
+        sender_public_key: order.sender_public_key.to_bytes().to_vec(),
+        matcher_public_key: order.matcher_public_key.to_bytes().to_vec(),
+        asset_pair: Some(waves::AssetPair {
+            amount_asset_id: asset_id(order.amount_asset.as_ref()),
+            price_asset_id: asset_id(order.price_asset.as_ref()),
+        }),
+        order_side: order.order_type as i32,
+        amount: order.amount as i64,
+        price: order.price as i64,
+        timestamp: order.timestamp as i64,
+        expiration: order.expiration as i64,
+        matcher_fee: Some(amount(order.matcher_fee_asset.as_ref(), order.matcher_fee)),
+        version: i32::from(order.version),
+        proofs,
+        price_mode: order.price_mode as i32,
+    }
+}
+
+fn read_hash(bytes: &[u8], field: &str) -> Result<Hash, ParseError> {
+    if bytes.len() != HASH_LENGTH {
+        return Err(ParseError::InvalidProtobuf(format!(
+            "invalid length of {}",
+            field
+        )));
+    }
+    let mut hash = [0u8; HASH_LENGTH];
+    hash.copy_from_slice(bytes);
+    Ok(Hash::new(hash))
+}
+
+fn read_asset_opt(bytes: &[u8]) -> Result<Option<Asset>, ParseError> {
+    if bytes.is_empty() {
+        Ok(None)
+    } else {
+        read_hash(bytes, "asset id").map(Some)
+    }
+}
+
+fn read_amount(amount: Option<waves::Amount>) -> Result<(Option<Asset>, u64), ParseError> {
+    let amount = amount.ok_or_else(|| invalid("missing amount"))?;
+    Ok((read_asset_opt(&amount.asset_id)?, amount.amount as u64))
+}
+
+fn read_asset_amount(amount: Option<waves::Amount>) -> Result<(Asset, u64), ParseError> {
+    match read_amount(amount)? {
+        (Some(asset), amount) => Ok((asset, amount)),
+        (None, _) => Err(invalid("missing asset id")),
+    }
+}
+
+/// Rebuilds the address from the public key hash of a protobuf recipient
+fn read_recipient(
+    recipient: Option<waves::Recipient>,
+    chain_id: u8,
+) -> Result<Recipient, ParseError> {
+    match recipient.and_then(|r| r.recipient) {
+        Some(RecipientValue::PublicKeyHash(hash)) if hash.len() == PUBLIC_KEY_HASH_LENGTH => {
+            let mut buf = [0u8; ADDRESS_LENGTH];
+            buf[0] = ADDRESS_VERSION;
+            buf[1] = chain_id;
+            buf[2..22].copy_from_slice(&hash);
+            let checksum = &secure_hash(&buf[..22])[..4];
+            buf[22..].copy_from_slice(checksum);
+            Ok(Recipient::Address(Address::from_array(buf)))
+        }
+        Some(RecipientValue::PublicKeyHash(_)) => Err(invalid("invalid length of public key hash")),
+        Some(RecipientValue::Alias(alias)) => Ok(Recipient::Alias {
+            alias: Alias::new(&alias).map_err(|_| ParseError::InvalidAlias)?,
+            chain_id,
+        }),
+        None => Err(invalid("missing recipient")),
+    }
+}
+
+fn read_public_key(bytes: &[u8], field: &str) -> Result<PublicKeyAccount, ParseError> {
+    if bytes.len() != PUBLIC_KEY_LENGTH {
+        return Err(ParseError::InvalidProtobuf(format!(
+            "invalid length of {}",
+            field
+        )));
+    }
+    let mut public_key = [0u8; PUBLIC_KEY_LENGTH];
+    public_key.copy_from_slice(bytes);
+    Ok(PublicKeyAccount(public_key))
+}
+
+fn read_order(msg: waves::Order) -> Result<ProvenOrder, ParseError> {
+    let version = u8::try_from(msg.version)
+        .ok()
+        .filter(|v| (Version::V1 as u8..=Version::V4 as u8).contains(v))
+        .ok_or_else(|| {
+            ParseError::UnsupportedOrderVersion(u8::try_from(msg.version).unwrap_or(u8::MAX))
+        })?;
+    let asset_pair = msg
+        .asset_pair
+        .ok_or_else(|| invalid("missing asset pair"))?;
+    let order_type = match msg.order_side {
+        0 => OrderType::Buy,
+        1 => OrderType::Sell,
+        _ => return Err(invalid("invalid order side")),
+    };
+    let price_mode = match msg.price_mode {
+        0 => PriceMode::Default,
+        1 => PriceMode::FixedDecimals,
+        2 => PriceMode::AssetDecimals,
+        _ => return Err(invalid("invalid price mode")),
+    };
+    let (matcher_fee_asset, matcher_fee) = read_amount(msg.matcher_fee)?;
+    let order = Order {
+        version,
+        chain_id: u8::try_from(msg.chain_id).map_err(|_| invalid("invalid chain id"))?,
+        sender_public_key: read_public_key(&msg.sender_public_key, "order sender public key")?,
+        matcher_public_key: read_public_key(&msg.matcher_public_key, "matcher public key")?,
+        amount_asset: read_asset_opt(&asset_pair.amount_asset_id)?,
+        price_asset: read_asset_opt(&asset_pair.price_asset_id)?,
+        order_type,
+        price_mode,
+        amount: msg.amount as u64,
+        price: msg.price as u64,
+        timestamp: msg.timestamp as u64,
+        expiration: msg.expiration as u64,
+        matcher_fee,
+        matcher_fee_asset,
+    };
+    Ok(order.with_proofs(msg.proofs))
+}
+
+fn read_function_call(bytes: &[u8]) -> Result<Option<FunctionCall<'static>>, ParseError> {
+    let mut reader = Reader::new(bytes);
+    let call = if reader.boolean()? {
+        Some(reader.function_call()?.into_owned())
+    } else {
+        None
+    };
+    reader.finish()?;
+    Ok(call)
+}
+
+fn read_bytes_opt(bytes: Vec<u8>) -> Option<Cow<'static, [u8]>> {
+    if bytes.is_empty() {
+        None
+    } else {
+        Some(bytes.into())
+    }
+}
+
+fn read_data_entry(entry: waves::DataEntry) -> Result<DataEntry<'static>, ParseError> {
+    let key = entry.key.into();
+    match entry.value {
+        Some(Value::IntValue(value)) => Ok(DataEntry::Integer(key, value as u64)),
+        Some(Value::BoolValue(value)) => Ok(DataEntry::Boolean(key, value)),
+        Some(Value::BinaryValue(value)) => Ok(DataEntry::Binary(key, value.into())),
+        Some(Value::StringValue(value)) => Ok(DataEntry::String(key, value.into())),
+        None => Ok(DataEntry::Delete(key)),
+    }
+}
+
+impl<'a> Transaction<'a> {
+    /// Encodes the transaction as a protobuf `Transaction` message
+    pub(crate) fn to_protobuf(&self) -> Vec<u8> {
+        self.to_message().encode_to_vec()
+    }
+
+    /// Decodes a protobuf `Transaction` message
+    pub(crate) fn from_protobuf(bytes: &[u8]) -> Result<Transaction<'static>, ParseError> {
+        Transaction::from_message(waves::Transaction::decode(bytes)?)
+    }
+
+    fn to_message(&self) -> waves::Transaction {
+        use TransactionData::*;
+
+        let mut fee_asset = None;
+        let data = match &self.data {
+            Issue {
+                name,
+                description,
+                quantity,
+                decimals,
+                reissuable,
+                script,
+            } => waves::Data::Issue(waves::IssueTransactionData {
+                name: name.to_string(),
+                description: description.to_string(),
+                amount: *quantity as i64,
+                decimals: i32::from(*decimals),
+                reissuable: *reissuable,
+                script: bytes_opt(script),
+            }),
+            Transfer {
+                recipient: to,
+                asset,
+                amount: value,
+                fee_asset: transfer_fee_asset,
+                attachment,
+            } => {
+                fee_asset = transfer_fee_asset.as_ref();
+                waves::Data::Transfer(waves::TransferTransactionData {
+                    recipient: recipient(to),
+                    amount: Some(amount(asset.as_ref(), *value)),
+                    attachment: bytes_opt(attachment),
+                })
+            }
+            Reissue {
+                asset,
+                quantity,
+                reissuable,
+            } => waves::Data::Reissue(waves::ReissueTransactionData {
+                asset_amount: Some(amount(Some(asset), *quantity)),
+                reissuable: *reissuable,
+            }),
+            Burn { asset, quantity } => waves::Data::Burn(waves::BurnTransactionData {
+                asset_amount: Some(amount(Some(asset), *quantity)),
+            }),
+            Exchange {
+                order1,
+                order2,
+                amount,
+                price,
+                buy_matcher_fee,
+                sell_matcher_fee,
+            } => waves::Data::Exchange(waves::ExchangeTransactionData {
+                amount: *amount as i64,
+                price: *price as i64,
+                buy_matcher_fee: *buy_matcher_fee as i64,
+                sell_matcher_fee: *sell_matcher_fee as i64,
+                orders: vec![
+                    order(&order1.order, order1.proofs.clone()),
+                    order(&order2.order, order2.proofs.clone()),
+                ],
+            }),
+            Lease {
+                recipient: to,
+                amount: value,
+            } => waves::Data::Lease(waves::LeaseTransactionData {
+                recipient: recipient(to),
+                amount: *value as i64,
+            }),
+            CancelLease { lease_id } => {
+                waves::Data::LeaseCancel(waves::LeaseCancelTransactionData {
+                    lease_id: lease_id.to_bytes().to_vec(),
+                })
+            }
+            Alias { alias } => waves::Data::CreateAlias(waves::CreateAliasTransactionData {
+                alias: alias.to_string(),
+            }),
+            MassTransfer {
+                asset,
+                transfers,
+                attachment,
+            } => waves::Data::MassTransfer(waves::MassTransferTransactionData {
+                asset_id: asset_id(asset.as_ref()),
+                transfers: transfers
+                    .iter()
+                    .map(|(to, value)| waves::mass_transfer::Transfer {
+                        recipient: recipient(to),
+                        amount: *value as i64,
+                    })
+                    .collect(),
+                attachment: bytes_opt(attachment),
+            }),
+            Data { data } => waves::Data::DataTransaction(waves::DataTransactionData {
+                data: data.iter().map(data_entry).collect(),
+            }),
+            SetScript { script } => waves::Data::SetScript(waves::SetScriptTransactionData {
+                script: bytes_opt(script),
+            }),
+            Sponsor { asset, rate } => waves::Data::SponsorFee(waves::SponsorFeeTransactionData {
+                min_fee: Some(amount(Some(asset), rate.unwrap_or(0))),
+            }),
+            SetAssetScript { asset, script } => {
+                waves::Data::SetAssetScript(waves::SetAssetScriptTransactionData {
+                    asset_id: asset.to_bytes().to_vec(),
+                    script: bytes_opt(script),
+                })
+            }
+            InvokeScript {
+                dapp,
+                call,
+                payments,
+                fee_asset: invoke_fee_asset,
+            } => {
+                fee_asset = invoke_fee_asset.as_ref();
+                waves::Data::InvokeScript(waves::InvokeScriptTransactionData {
+                    d_app: recipient(dapp),
+                    function_call: function_call(call),
+                    payments: payments
+                        .iter()
+                        .map(|(asset, value)| amount(asset.as_ref(), *value))
+                        .collect(),
+                })
+            }
+            UpdateAssetInfo {
+                asset,
+                name,
+                description,
+            } => waves::Data::UpdateAssetInfo(waves::UpdateAssetInfoTransactionData {
+                asset_id: asset.to_bytes().to_vec(),
+                name: name.to_string(),
+                description: description.to_string(),
+            }),
+        };
+
+        waves::Transaction {
+            chain_id: i32::from(self.chain_id),
+            sender_public_key: self.sender_public_key.to_bytes().to_vec(),
+            fee: Some(amount(fee_asset, self.fee)),
+            timestamp: self.timestamp as i64,
+            version: i32::from(self.version),
+            data: Some(data),
+        }
+    }
+
+    fn from_message(msg: waves::Transaction) -> Result<Transaction<'static>, ParseError> {
+        use TransactionData::*;
+
+        let chain_id = u8::try_from(msg.chain_id).map_err(|_| invalid("invalid chain id"))?;
+        let sender_public_key = read_public_key(&msg.sender_public_key, "sender public key")?;
+        let (fee_asset, fee) = read_amount(msg.fee)?;
+
+        let (tx_type, data) = match msg
+            .data
+            .ok_or_else(|| invalid("missing transaction data"))?
+        {
+            waves::Data::Issue(d) => {
+                let decimals = u8::try_from(d.decimals).map_err(|_| invalid("invalid decimals"))?;
+                let data = Issue {
+                    name: d.name.into(),
+                    description: d.description.into(),
+                    quantity: d.amount as u64,
+                    decimals,
+                    reissuable: d.reissuable,
+                    script: read_bytes_opt(d.script),
+                };
+                (Type::Issue, data)
+            }
+            waves::Data::Transfer(d) => {
+                let (asset, amount) = read_amount(d.amount)?;
+                let data = Transfer {
+                    recipient: read_recipient(d.recipient, chain_id)?,
+                    asset,
+                    amount,
+                    fee_asset,
+                    attachment: read_bytes_opt(d.attachment),
+                };
+                (Type::Transfer, data)
+            }
+            waves::Data::Reissue(d) => {
+                let (asset, quantity) = read_asset_amount(d.asset_amount)?;
+                let data = Reissue {
+                    asset,
+                    quantity,
+                    reissuable: d.reissuable,
+                };
+                (Type::Reissue, data)
+            }
+            waves::Data::Burn(d) => {
+                let (asset, quantity) = read_asset_amount(d.asset_amount)?;
+                (Type::Burn, Burn { asset, quantity })
+            }
+            waves::Data::Exchange(d) => {
+                let mut orders = d.orders.into_iter().map(read_order);
+                let (order1, order2) = match (orders.next(), orders.next(), orders.next()) {
+                    (Some(order1), Some(order2), None) => (order1?, order2?),
+                    _ => return Err(invalid("exchange must have two orders")),
+                };
+                let data = Exchange {
+                    order1,
+                    order2,
+                    amount: d.amount as u64,
+                    price: d.price as u64,
+                    buy_matcher_fee: d.buy_matcher_fee as u64,
+                    sell_matcher_fee: d.sell_matcher_fee as u64,
+                };
+                (Type::Exchange, data)
+            }
+            waves::Data::Lease(d) => {
+                let data = Lease {
+                    recipient: read_recipient(d.recipient, chain_id)?,
+                    amount: d.amount as u64,
+                };
+                (Type::Lease, data)
+            }
+            waves::Data::LeaseCancel(d) => {
+                let lease_id = read_hash(&d.lease_id, "lease id")?;
+                (Type::LeaseCancel, CancelLease { lease_id })
+            }
+            waves::Data::CreateAlias(d) => (
+                Type::Alias,
+                Alias {
+                    alias: d.alias.into(),
+                },
+            ),
+            waves::Data::MassTransfer(d) => {
+                let transfers = d
+                    .transfers
+                    .into_iter()
+                    .map(|t| Ok((read_recipient(t.recipient, chain_id)?, t.amount as u64)))
+                    .collect::<Result<_, ParseError>>()?;
+                let data = MassTransfer {
+                    asset: read_asset_opt(&d.asset_id)?,
+                    transfers,
+                    attachment: read_bytes_opt(d.attachment),
+                };
+                (Type::MassTransfer, data)
+            }
+            waves::Data::DataTransaction(d) => {
+                let data = d
+                    .data
+                    .into_iter()
+                    .map(read_data_entry)
+                    .collect::<Result<_, ParseError>>()?;
+                (Type::Data, Data { data })
+            }
+            waves::Data::SetScript(d) => (
+                Type::SetScript,
+                SetScript {
+                    script: read_bytes_opt(d.script),
+                },
+            ),
+            waves::Data::SponsorFee(d) => {
+                let (asset, rate) = read_asset_amount(d.min_fee)?;
+                let rate = if rate == 0 { None } else { Some(rate) };
+                (Type::Sponsor, Sponsor { asset, rate })
+            }
+            waves::Data::SetAssetScript(d) => {
+                let data = SetAssetScript {
+                    asset: read_hash(&d.asset_id, "asset id")?,
+                    script: read_bytes_opt(d.script),
+                };
+                (Type::SetAssetScript, data)
+            }
+            waves::Data::InvokeScript(d) => {
+                let payments = d
+                    .payments
+                    .into_iter()
+                    .map(|payment| read_amount(Some(payment)))
+                    .collect::<Result<_, ParseError>>()?;
+                let data = InvokeScript {
+                    dapp: read_recipient(d.d_app, chain_id)?,
+                    call: read_function_call(&d.function_call)?,
+                    payments,
+                    fee_asset,
+                };
+                (Type::InvokeScript, data)
+            }
+            waves::Data::UpdateAssetInfo(d) => {
+                let data = UpdateAssetInfo {
+                    asset: read_hash(&d.asset_id, "asset id")?,
+                    name: d.name.into(),
+                    description: d.description.into(),
+                };
+                (Type::UpdateAssetInfo, data)
+            }
+        };
+
+        let version = u8::try_from(msg.version)
+            .ok()
+            .filter(|v| super::is_protobuf_version(tx_type, *v))
+            .ok_or(ParseError::UnsupportedVersion(
+                tx_type as u8,
+                u8::try_from(msg.version).unwrap_or(u8::MAX),
+            ))?;
+        if fee_asset.is_some() && !matches!(tx_type, Type::Transfer | Type::InvokeScript) {
+            return Err(invalid(
+                "fee asset is only supported for Transfer and Invoke Script",
+            ));
+        }
+
+        Ok(Transaction {
+            data,
+            fee,
+            timestamp: msg.timestamp as u64,
+            sender_public_key,
+            chain_id,
+            type_id: tx_type as u8,
+            version,
+        })
+    }
+}
+
+impl<'a> ProvenTransaction<'a> {
+    /// Encodes the transaction as a protobuf `SignedTransaction` message
+    pub(crate) fn to_protobuf(&self) -> Vec<u8> {
+        waves::SignedTransaction {
+            waves_transaction: Some(self.tx.to_message()),
+            proofs: self.proofs.clone(),
+        }
+        .encode_to_vec()
+    }
+
+    /// Decodes a protobuf `SignedTransaction` message
+    pub(crate) fn from_protobuf(bytes: &[u8]) -> Result<ProvenTransaction<'static>, ParseError> {
+        let msg = waves::SignedTransaction::decode(bytes)?;
+        let tx = msg
+            .waves_transaction
+            .ok_or_else(|| invalid("missing transaction"))?;
+        Ok(ProvenTransaction {
+            tx: Transaction::from_message(tx)?,
+            proofs: msg.proofs,
+        })
+    }
+}
+
+impl Order {
+    /// Encodes the order as a protobuf `Order` message without proofs
+    pub(crate) fn to_protobuf(&self) -> Vec<u8> {
+        order(self, vec![]).encode_to_vec()
+    }
+}
+
+impl ProvenOrder {
+    /// Encodes the order as a protobuf `Order` message with proofs
+    pub(crate) fn to_protobuf(&self) -> Vec<u8> {
+        order(&self.order, self.proofs.clone()).encode_to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::account::{blake_hash, PrivateKeyAccount, TESTNET};
+    use crate::transaction::{Arg, TransactionId, Version};
+
+    #[test]
+    fn test_protobuf_bytes() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let tx = Transaction::new_alias(&pk, "lilias", TESTNET, 100000, 1536000000000)
+            .with_version(Version::V3);
+
+        let mut expected = vec![0x08, TESTNET, 0x12, 0x20];
+        expected.extend_from_slice(&[1u8; 32]);
+        expected.extend_from_slice(&[0x1a, 0x04, 0x10, 0xa0, 0x8d, 0x06]);
+        expected.extend_from_slice(&[0x20, 0x80, 0x80, 0xf0, 0x85, 0xda, 0x2c]);
+        expected.extend_from_slice(&[0x28, 0x03]);
+        expected.extend_from_slice(&[0xf2, 0x06, 0x08, 0x0a, 0x06]);
+        expected.extend_from_slice(b"lilias");
+
+        let bytes = tx.to_bytes();
+        assert_eq!(bytes, expected);
+        assert_eq!(tx.id().to_bytes(), blake_hash(&expected).as_slice());
+        assert_eq!(Transaction::from_bytes(&bytes), Ok(tx.clone()));
+
+        let signed = tx.with_proofs(vec![vec![7u8; 64]]);
+        let mut expected_signed = vec![0x0a, expected.len() as u8];
+        expected_signed.extend_from_slice(&expected);
+        expected_signed.extend_from_slice(&[0x12, 0x40]);
+        expected_signed.extend_from_slice(&[7u8; 64]);
+        assert_eq!(signed.to_bytes(), expected_signed);
+    }
+
+    #[test]
+    fn test_protobuf_roundtrip() {
+        let sender = PrivateKeyAccount::from_seed("test");
+        let pk = *sender.public_key();
+        let asset = Asset::new([2u8; 32]);
+        let lease = TransactionId::new([3u8; 32]);
+        let recipient = Recipient::from(pk.to_address(TESTNET));
+        let script = vec![1, 6, 183, 111, 203, 71];
+        let entry = DataEntry::Binary("bin".into(), vec![4u8; 32].into());
+        let delete = DataEntry::Delete("old".into());
+        let dapp = recipient.clone();
+        let alias = Recipient::Alias {
+            alias: Alias::new("merchant").unwrap(),
+            chain_id: TESTNET,
+        };
+        let call = FunctionCall::new("call", vec![Arg::List(vec![Arg::Integer(1)])]);
+        let fee = 100000;
+        let ts = 1536000000000;
+        let order = Order::new(
+            &pk,
+            &pk,
+            None,
+            Some(&asset),
+            OrderType::Sell,
+            10,
+            20,
+            ts,
+            ts,
+            30,
+            Some(&asset),
+            TESTNET,
+        );
+        let v1 = Order {
+            matcher_fee_asset: None,
+            ..order.clone()
+        };
+        let order1 = sender.sign_order(v1.with_version(Version::V1));
+        let order2 = sender.sign_order(order);
+
+        let txs = vec![
+            Transaction::new_issue(
+                &pk, "coin", "coin", 100000000, 8, true, TESTNET, fee, ts, None,
+            ),
+            Transaction::new_transfer(
+                &pk,
+                &recipient,
+                Some(&asset),
+                10,
+                Some(&asset),
+                fee,
+                Some("atta ch me"),
+                ts,
+            ),
+            Transaction::new_reissue(&pk, &asset, 100000000, false, TESTNET, fee, ts),
+            Transaction::new_burn(&pk, &asset, 100000000, TESTNET, fee, ts),
+            Transaction::new_exchange(&pk, &order1, &order2, 10, 20, 30, 30, fee, ts),
+            Transaction::new_lease(&pk, &recipient, 10, TESTNET, fee, ts),
+            Transaction::new_lease_cancel(&pk, &lease, TESTNET, fee, ts),
+            Transaction::new_alias(&pk, "lilias", TESTNET, fee, ts),
+            Transaction::new_mass_transfer(
+                &pk,
+                None,
+                vec![(&recipient, 10), (&alias, 20)],
+                None,
+                fee,
+                ts,
+            ),
+            Transaction::new_data(&pk, vec![&entry, &delete], fee, ts).with_chain_id(TESTNET),
+            Transaction::new_script(&pk, Some(&script), TESTNET, fee, ts),
+            Transaction::new_sponsor(&pk, &asset, Some(100), fee, ts).with_chain_id(TESTNET),
+            Transaction::new_set_asset_script(&pk, &asset, Some(&script), TESTNET, fee, ts),
+            Transaction::new_invoke_script(
+                &pk,
+                &dapp,
+                Some(&call),
+                vec![(None, 10), (Some(&asset), 20)],
+                Some(&asset),
+                fee,
+                ts,
+            ),
+            Transaction::new_invoke_script(&pk, &alias, None, vec![], None, fee, ts),
+            Transaction::new_update_asset_info(&pk, &asset, "name", "desc", TESTNET, fee, ts),
+        ];
+
+        for tx in txs {
+            let legacy_id = tx.id();
+            let tx = tx.with_version(Version::V3);
+            let bytes = tx.to_bytes();
+            assert_eq!(bytes[..2], [0x08, TESTNET]);
+            assert_ne!(tx.id(), legacy_id);
+            assert_eq!(tx.id().to_bytes(), blake_hash(&bytes).as_slice());
+            assert_eq!(Transaction::from_bytes(&bytes), Ok(tx.clone()));
+
+            let signed = sender.sign_transaction(tx);
+            let signed_bytes = signed.to_bytes();
+            assert_eq!(
+                ProvenTransaction::from_bytes(&signed_bytes),
+                Ok(signed.clone())
+            );
+
+            let json = serde_json::to_string(&signed).unwrap();
+            assert_eq!(
+                serde_json::from_str::<ProvenTransaction>(&json).unwrap(),
+                signed
+            );
+        }
+
+        // Update Asset Info has no legacy encoding whatever its version
+        let tx = Transaction::new_update_asset_info(&pk, &asset, "name", "desc", TESTNET, fee, ts)
+            .with_version(Version::V1);
+        assert_eq!(tx.to_bytes(), tx.to_protobuf());
+        assert_eq!(Transaction::from_bytes(&tx.to_bytes()), Ok(tx));
+    }
+
+    #[test]
+    fn test_protobuf_errors() {
+        let pk = PublicKeyAccount([1u8; 32]);
+        let tx = Transaction::new_alias(&pk, "lilias", TESTNET, 100000, 1536000000000)
+            .with_version(Version::V3);
+        let bytes = tx.to_bytes();
+
+        assert!(matches!(
+            Transaction::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(ParseError::InvalidProtobuf(_))
+        ));
+        assert_eq!(
+            Transaction::from_bytes(&bytes[..bytes.len() - 11]),
+            Err(ParseError::InvalidProtobuf(
+                "missing transaction data".to_string()
+            ))
+        );
+
+        let legacy = tx.clone().with_version(Version::V2).to_protobuf();
+        assert_eq!(
+            Transaction::from_bytes(&legacy),
+            Err(ParseError::UnsupportedVersion(10, 2))
+        );
+        let mut msg = waves::Transaction::decode(bytes.as_slice()).unwrap();
+        msg.version = 256;
+        assert_eq!(
+            Transaction::from_bytes(&msg.encode_to_vec()),
+            Err(ParseError::UnsupportedVersion(10, u8::MAX))
+        );
+
+        let order = Order::new(
+            &pk,
+            &pk,
+            None,
+            None,
+            OrderType::Buy,
+            10,
+            20,
+            0,
+            0,
+            30,
+            None,
+            TESTNET,
+        );
+        let mut msg = super::order(&order, vec![]);
+        msg.version = 256;
+        assert_eq!(
+            read_order(msg).err(),
+            Some(ParseError::UnsupportedOrderVersion(u8::MAX))
+        );
+
+        // a `SignedTransaction` with an empty proof only
+        assert_eq!(
+            ProvenTransaction::from_bytes(&[0x12, 0x00]),
+            Err(ParseError::InvalidProtobuf(
+                "missing transaction".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use crate::account::Address;
+use crate::error::Error;
+use crate::util::Alias;
+
+use std::fmt;
+
+/// Recipient of a transaction: either an address, or an alias of an address on the given chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recipient {
+    Address(Address),
+    Alias { alias: Alias, chain_id: u8 },
+}
+
+impl Recipient {
+    /// Get chain ID.
+    pub fn chain_id(&self) -> u8 {
+        match self {
+            Recipient::Address(address) => address.chain_id(),
+            Recipient::Alias { chain_id, .. } => *chain_id,
+        }
+    }
+
+    /// Create a [`Recipient`] from a base58 address or an alias with the `alias:<chain ID>:`
+    /// prefix, as the node formats them.
+    /// ```
+    /// use wavesplatform::account::MAINNET;
+    /// use wavesplatform::transaction::Recipient;
+    /// let recipient = Recipient::from_string("alias:W:merchant").unwrap();
+    /// assert_eq!(recipient.chain_id(), MAINNET);
+    /// ```
+    pub fn from_string(value: &str) -> Result<Recipient, Error> {
+        if value.starts_with("alias:") {
+            let alias = Alias::new(value).map_err(|_| Error::InvalidAlias)?;
+            Ok(Recipient::Alias {
+                alias,
+                chain_id: value.as_bytes()[6],
+            })
+        } else {
+            Ok(Recipient::Address(Address::from_string(value)?))
+        }
+    }
+}
+
+impl From<Address> for Recipient {
+    fn from(address: Address) -> Recipient {
+        Recipient::Address(address)
+    }
+}
+
+/// Formats the recipient as the node does: an address in base58, or an alias with the
+/// `alias:<chain ID>:` prefix
+impl fmt::Display for Recipient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recipient::Address(address) => write!(f, "{}", address),
+            Recipient::Alias { alias, chain_id } => {
+                write!(f, "{}", alias.to_string_with_prefix(*chain_id))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::TESTNET;
+
+    #[test]
+    fn test_recipient() {
+        let address = Address::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D").unwrap();
+        let recipient = Recipient::from(address);
+        assert_eq!(recipient.chain_id(), TESTNET);
+        assert_eq!(recipient.to_string(), "3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D");
+
+        let recipient = Recipient::Alias {
+            alias: Alias::new("merchant").unwrap(),
+            chain_id: TESTNET,
+        };
+        assert_eq!(recipient.chain_id(), TESTNET);
+        assert_eq!(recipient.to_string(), "alias:T:merchant");
+        assert_eq!(
+            Recipient::from_string("alias:T:merchant").unwrap(),
+            recipient
+        );
+        assert!(matches!(
+            Recipient::from_string("alias:T:a"),
+            Err(Error::InvalidAlias)
+        ));
+        assert_eq!(
+            Recipient::from_string("3MzGEv9wnaqrYFYujAXSH5RQfHaVKNQvx3D").unwrap(),
+            Recipient::Address(address)
+        );
+    }
+}
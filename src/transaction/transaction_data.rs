@@ -1,68 +1,197 @@
-use crate::account::Address;
-use crate::transaction::{Asset, DataEntry, TransactionId};
+use crate::transaction::{Asset, DataEntry, FunctionCall, ProvenOrder, Recipient, TransactionId};
+
+use std::borrow::Cow;
 
 /// Data specific to a particular transaction type
-#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TransactionData<'a> {
     Issue {
-        name: &'a str,
-        description: &'a str,
+        name: Cow<'a, str>,
+        description: Cow<'a, str>,
         quantity: u64,
         decimals: u8,
         reissuable: bool,
-        chain_id: u8,
-        script: Option<&'a [u8]>,
+        script: Option<Cow<'a, [u8]>>,
     },
     Transfer {
-        recipient: &'a Address,
-        asset: Option<&'a Asset>,
+        recipient: Recipient,
+        asset: Option<Asset>,
         amount: u64,
-        fee_asset: Option<&'a Asset>,
-        attachment: Option<&'a str>,
+        fee_asset: Option<Asset>,
+        attachment: Option<Cow<'a, [u8]>>,
     },
     Reissue {
-        asset: &'a Asset,
+        asset: Asset,
         quantity: u64,
         reissuable: bool,
-        chain_id: u8,
     },
     Burn {
-        asset: &'a Asset,
+        asset: Asset,
         quantity: u64,
-        chain_id: u8,
+    },
+    Exchange {
+        order1: ProvenOrder,
+        order2: ProvenOrder,
+        amount: u64,
+        price: u64,
+        buy_matcher_fee: u64,
+        sell_matcher_fee: u64,
     },
     Lease {
-        recipient: &'a Address,
+        recipient: Recipient,
         amount: u64,
-        chain_id: u8,
     },
     CancelLease {
-        lease_id: &'a TransactionId,
-        chain_id: u8,
+        lease_id: TransactionId,
     },
     Alias {
-        alias: &'a str,
-        chain_id: u8,
+        alias: Cow<'a, str>,
     },
     MassTransfer {
-        asset: Option<&'a Asset>,
-        transfers: Vec<(&'a Address, u64)>,
-        attachment: Option<&'a str>,
+        asset: Option<Asset>,
+        transfers: Vec<(Recipient, u64)>,
+        attachment: Option<Cow<'a, [u8]>>,
     },
     Data {
-        data: Vec<&'a DataEntry<'a>>,
+        data: Vec<DataEntry<'a>>,
     },
     SetScript {
-        script: Option<&'a [u8]>,
-        chain_id: u8,
+        script: Option<Cow<'a, [u8]>>,
     },
     Sponsor {
-        asset: &'a Asset,
+        asset: Asset,
         rate: Option<u64>,
     },
     SetAssetScript {
-        asset: &'a Asset,
-        script: Option<&'a [u8]>,
-        chain_id: u8,
+        asset: Asset,
+        script: Option<Cow<'a, [u8]>>,
     },
+    InvokeScript {
+        dapp: Recipient,
+        call: Option<FunctionCall<'a>>,
+        payments: Vec<(Option<Asset>, u64)>,
+        fee_asset: Option<Asset>,
+    },
+    UpdateAssetInfo {
+        asset: Asset,
+        name: Cow<'a, str>,
+        description: Cow<'a, str>,
+    },
+}
+
+impl<'a> TransactionData<'a> {
+    /// Converts the data to one that owns all its strings and byte arrays
+    pub fn into_owned(self) -> TransactionData<'static> {
+        use TransactionData::*;
+
+        match self {
+            Issue {
+                name,
+                description,
+                quantity,
+                decimals,
+                reissuable,
+                script,
+            } => Issue {
+                name: owned_str(name),
+                description: owned_str(description),
+                quantity,
+                decimals,
+                reissuable,
+                script: script.map(owned_bytes),
+            },
+            Transfer {
+                recipient,
+                asset,
+                amount,
+                fee_asset,
+                attachment,
+            } => Transfer {
+                recipient,
+                asset,
+                amount,
+                fee_asset,
+                attachment: attachment.map(owned_bytes),
+            },
+            Reissue {
+                asset,
+                quantity,
+                reissuable,
+            } => Reissue {
+                asset,
+                quantity,
+                reissuable,
+            },
+            Burn { asset, quantity } => Burn { asset, quantity },
+            Exchange {
+                order1,
+                order2,
+                amount,
+                price,
+                buy_matcher_fee,
+                sell_matcher_fee,
+            } => Exchange {
+                order1,
+                order2,
+                amount,
+                price,
+                buy_matcher_fee,
+                sell_matcher_fee,
+            },
+            Lease { recipient, amount } => Lease { recipient, amount },
+            CancelLease { lease_id } => CancelLease { lease_id },
+            Alias { alias } => Alias {
+                alias: owned_str(alias),
+            },
+            MassTransfer {
+                asset,
+                transfers,
+                attachment,
+            } => MassTransfer {
+                asset,
+                transfers,
+                attachment: attachment.map(owned_bytes),
+            },
+            Data { data } => Data {
+                data: data.into_iter().map(DataEntry::into_owned).collect(),
+            },
+            SetScript { script } => SetScript {
+                script: script.map(owned_bytes),
+            },
+            Sponsor { asset, rate } => Sponsor { asset, rate },
+            SetAssetScript { asset, script } => SetAssetScript {
+                asset,
+                script: script.map(owned_bytes),
+            },
+            InvokeScript {
+                dapp,
+                call,
+                payments,
+                fee_asset,
+            } => InvokeScript {
+                dapp,
+                call: call.map(FunctionCall::into_owned),
+                payments,
+                fee_asset,
+            },
+            UpdateAssetInfo {
+                asset,
+                name,
+                description,
+            } => UpdateAssetInfo {
+                asset,
+                name: owned_str(name),
+                description: owned_str(description),
+            },
+        }
+    }
+}
+
+fn owned_str(value: Cow<str>) -> Cow<'static, str> {
+    Cow::Owned(value.into_owned())
+}
+
+fn owned_bytes(value: Cow<[u8]>) -> Cow<'static, [u8]> {
+    Cow::Owned(value.into_owned())
 }
@@ -1,3 +1,6 @@
+use crate::transaction::ParseError;
+
+use std::convert::TryFrom;
 use std::fmt;
 
 /// Transaction type
@@ -12,7 +15,7 @@ use std::fmt;
 /// * Transfer transaction sends a certain amount of token to another account.
 ///
 /// Content of transaction depends on its type.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Type {
     /// Issue Transaction
     Issue = 3,
@@ -22,6 +25,8 @@ pub enum Type {
     Reissue = 5,
     /// Burn Transaction
     Burn = 6,
+    /// Exchange Transaction
+    Exchange = 7,
     /// Lease Transaction
     Lease = 8,
     /// Lease Cancel Transaction
@@ -38,6 +43,10 @@ pub enum Type {
     Sponsor = 14,
     /// Set Asset Script Transaction
     SetAssetScript = 15,
+    /// Invoke Script Transaction
+    InvokeScript = 16,
+    /// Update Asset Info Transaction
+    UpdateAssetInfo = 17,
 }
 
 impl fmt::Display for Type {
@@ -47,6 +56,7 @@ impl fmt::Display for Type {
             Type::Transfer => write!(f, "Transfer Transaction"),
             Type::Reissue => write!(f, "Reissue Transaction"),
             Type::Burn => write!(f, "Burn Transaction"),
+            Type::Exchange => write!(f, "Exchange Transaction"),
             Type::Lease => write!(f, "Lease Transaction"),
             Type::LeaseCancel => write!(f, "Lease Cancel Transaction"),
             Type::Alias => write!(f, "Create Alias Transaction"),
@@ -55,6 +65,33 @@ impl fmt::Display for Type {
             Type::SetScript => write!(f, "Set Script Transaction"),
             Type::Sponsor => write!(f, "Sponsor Fee Transaction"),
             Type::SetAssetScript => write!(f, "Set Asset Script Transaction"),
+            Type::InvokeScript => write!(f, "Invoke Script Transaction"),
+            Type::UpdateAssetInfo => write!(f, "Update Asset Info Transaction"),
+        }
+    }
+}
+
+impl TryFrom<u8> for Type {
+    type Error = ParseError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            3 => Ok(Type::Issue),
+            4 => Ok(Type::Transfer),
+            5 => Ok(Type::Reissue),
+            6 => Ok(Type::Burn),
+            7 => Ok(Type::Exchange),
+            8 => Ok(Type::Lease),
+            9 => Ok(Type::LeaseCancel),
+            10 => Ok(Type::Alias),
+            11 => Ok(Type::MassTransfer),
+            12 => Ok(Type::Data),
+            13 => Ok(Type::SetScript),
+            14 => Ok(Type::Sponsor),
+            15 => Ok(Type::SetAssetScript),
+            16 => Ok(Type::InvokeScript),
+            17 => Ok(Type::UpdateAssetInfo),
+            _ => Err(ParseError::UnknownType(value)),
         }
     }
 }
@@ -68,4 +105,11 @@ mod tests {
         let type_id = Type::Issue;
         assert_eq!(type_id.to_string(), "Issue Transaction");
     }
+
+    #[test]
+    fn test_type_try_from() {
+        assert_eq!(Type::try_from(12), Ok(Type::Data));
+        assert_eq!(Type::try_from(7), Ok(Type::Exchange));
+        assert_eq!(Type::try_from(2), Err(ParseError::UnknownType(2)));
+    }
 }
@@ -1,10 +1,12 @@
 use std::fmt;
 
 /// Transaction version
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Version {
     V1 = 1,
     V2 = 2,
+    V3 = 3,
+    V4 = 4,
 }
 
 impl fmt::Display for Version {
@@ -12,6 +14,8 @@ impl fmt::Display for Version {
         match self {
             Version::V1 => write!(f, "Version 1"),
             Version::V2 => write!(f, "Version 2"),
+            Version::V3 => write!(f, "Version 3"),
+            Version::V4 => write!(f, "Version 4"),
         }
     }
 }
@@ -7,7 +7,8 @@ use ed25519_dalek::*;
 pub use alias::*;
 pub use amount::*;
 
-/// Signature verify function
+/// Signature verify function. Returns `false` for a public key or signature that is not a valid
+/// curve point.
 pub fn sig_verify(
     message: &[u8],
     public_key: &[u8; PUBLIC_KEY_LENGTH],
@@ -18,18 +19,15 @@ pub fn sig_verify(
     sig.copy_from_slice(signature);
     sig[63] &= 0x7f;
 
-    let mut ed_pubkey = MontgomeryPoint(*public_key)
-        .to_edwards(sign)
-        .unwrap()
-        .compress()
-        .to_bytes();
+    let mut ed_pubkey = match MontgomeryPoint(*public_key).to_edwards(sign) {
+        Some(point) => point.compress().to_bytes(),
+        None => return false,
+    };
     ed_pubkey[31] &= 0x7F; // should be zero already, but just in case
     ed_pubkey[31] |= sign;
 
     VerifyingKey::from_bytes(&ed_pubkey)
-        .unwrap()
-        .verify(message, &Signature::from_bytes(&sig))
-        .is_ok()
+        .is_ok_and(|key| key.verify(message, &Signature::from_bytes(&sig)).is_ok())
 }
 
 #[cfg(test)]
@@ -43,7 +41,7 @@ mod tests {
         let msg = "uncle".as_bytes();
         let mut sk = [0u8; SECRET_KEY_LENGTH];
         sk.copy_from_slice(
-            &"6zFSymZAoaua3gtJPbAUwM584tRETdKYdEG9BeEnZaGW"
+            "6zFSymZAoaua3gtJPbAUwM584tRETdKYdEG9BeEnZaGW"
                 .from_base58()
                 .unwrap()
                 .as_slice(),
@@ -76,4 +74,13 @@ mod tests {
                 .from_base58().unwrap().as_slice());
         assert!(sig_verify(msg, &pk, &sig));
     }
+
+    #[test]
+    fn test_sig_verify_invalid_key() {
+        // u = -1 has no Edwards equivalent
+        let mut pk = [0xffu8; PUBLIC_KEY_LENGTH];
+        pk[0] = 0xec;
+        pk[31] = 0x7f;
+        assert!(!sig_verify(b"uncle", &pk, &[0u8; SIGNATURE_LENGTH]));
+    }
 }
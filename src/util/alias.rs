@@ -21,7 +21,7 @@ impl Alias {
     /// Create an [`Alias`] from the string
     pub fn new(alias: &str) -> Result<Alias, AliasError> {
         if Self::is_valid(REGEXP, alias)? {
-            Ok(Alias(format!("{}", alias)))
+            Ok(Alias(alias.to_string()))
         } else if Self::is_valid(REGEXP_WITH_PREFIX, alias)? {
             let value = Self::replace_prefix(alias)?;
             Ok(Alias(value))
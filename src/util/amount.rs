@@ -23,12 +23,12 @@ impl Amount {
 
     /// The maximum value of an [`Amount`].
     pub fn max_value() -> Amount {
-        Amount(u64::max_value())
+        Amount(u64::MAX)
     }
 
     /// The minimum value of an [`Amount`].
     pub fn min_value() -> Amount {
-        Amount(u64::min_value())
+        Amount(u64::MIN)
     }
 
     /// Get the number of WAVELET in this [`Amount`].